@@ -2,10 +2,10 @@ use std::sync::mpsc::channel;
 
 use art::{
     palette::color::Color,
+    raster::canvas::Canvas,
     shapes::{point::Point, rectangle::Rectangle},
     util::filename::generate_filename,
 };
-use image::{ImageBuffer, Pixel, RgbImage};
 use rand::{thread_rng, Rng};
 use threadpool::ThreadPool;
 
@@ -20,9 +20,9 @@ fn main() {
         ..Default::default()
     };
 
-    let mut img: RgbImage = ImageBuffer::new(width as u32, height as u32);
+    let mut canvas = Canvas::new(width as u32, height as u32);
 
-    img.fill(255);
+    canvas.fill(Color::Hex("#fff"));
 
     let inner_bounds = bounds.scale(0.8);
     let mut rects: Vec<Rectangle> = vec![];
@@ -72,16 +72,17 @@ fn main() {
     receiver.iter().take(count).for_each(|points| {
         for point in points {
             if inner_bounds.x_range().contains(&point.0) {
-                img.put_pixel(
-                    point.0 as u32,
-                    point.1 as u32,
-                    image::Rgba([100, 100, 100, 10]).to_rgb(),
+                canvas.blend_pixel(
+                    point.0 as i64,
+                    point.1 as i64,
+                    Color::Rgba(100.0, 100.0, 100.0, 10.0 / 255.0),
                 );
             }
         }
     });
 
-    img.save(generate_filename("grid", "png"))
+    canvas
+        .save(generate_filename("grid", "png"))
         .expect("could not save file");
 }
 
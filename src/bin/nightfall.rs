@@ -1,28 +1,23 @@
 use art::{
     palette::color::Color,
     pointmap::pointmap::Pointmap,
+    raster::canvas::Canvas,
     shapes::{
         circle::Circle, path::Path, path_style::PathStyle, point::Point, rectangle::Rectangle,
         shape::Shape,
     },
     util::{filename::generate_filename, math::weighted_random},
 };
-use image::{ImageBuffer, RgbImage};
 use rand::Rng;
 
 fn main() {
     let bounds = Rectangle::new(Point(0.0, 0.0), 3000.0, 3000.0 * 1.4);
-    let mut img: RgbImage = ImageBuffer::new(bounds.width as u32, bounds.height as u32);
+    let mut canvas = Canvas::new(bounds.width as u32, bounds.height as u32);
     let mut rng = rand::thread_rng();
 
-    imageproc::drawing::draw_filled_rect_mut(
-        &mut img,
-        bounds.into(),
-        Color::HSLa(37, 80.0, 97.0, 1.0).into(),
-    );
+    canvas.fill(Color::HSLa(37, 80.0, 97.0, 1.0));
 
     let spheres = (0..4)
-        .into_iter()
         .map(|_| {
             Circle::new(
                 Point(
@@ -39,7 +34,6 @@ fn main() {
     let mut pointmap = Pointmap::new(&bounds, &mut points, 2);
 
     let rects = (0..4)
-        .into_iter()
         .map(|i| {
             Rectangle::new(
                 Point(
@@ -81,10 +75,11 @@ fn main() {
         neighbors.iter().take(10).for_each(|neighbor| {
             let line = &mut vec![**point, **neighbor];
             let path = Path::new(line.clone(), style);
-            path.render(&mut img);
+            path.render_blended(&mut canvas);
         });
     });
 
-    img.save(generate_filename("nightfall", "jpg"))
+    canvas
+        .save(generate_filename("nightfall", "jpg"))
         .expect("Could not save image");
 }
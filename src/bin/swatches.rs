@@ -0,0 +1,36 @@
+use art::{
+    palette::{registry::PaletteRegistry, swatch::SwatchSheet},
+    util::filename::generate_filename,
+};
+
+/// Draws a contact sheet of the palettes given as arguments, or of all palettes.
+fn main() {
+    let registry = PaletteRegistry::default();
+    let names = std::env::args().skip(1).collect::<Vec<String>>();
+    let names = if names.is_empty() {
+        registry.names().into_iter().map(String::from).collect()
+    } else {
+        names
+    };
+
+    let mut sheet = SwatchSheet::new();
+
+    for (row, name) in names.iter().enumerate() {
+        let Some(entry) = registry.entry(name) else {
+            eprintln!(
+                "unknown palette {name}, one of: {}",
+                registry.names().join(", ")
+            );
+            std::process::exit(1);
+        };
+
+        println!("{}: {} - {}", row + 1, entry.name(), entry.description());
+        let (background, palette) = entry.build();
+        sheet = sheet.add_palette(background, palette.as_ref());
+    }
+
+    sheet.to_document("swatches").save();
+    sheet
+        .save_png(generate_filename("swatches", "png"))
+        .expect("could not save file");
+}
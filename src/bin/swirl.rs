@@ -1,5 +1,8 @@
 use art::{
-    palette::palettes,
+    palette::{
+        color_map::{ColorMap, PaletteOrder},
+        palettes,
+    },
     pointmap::pointmap::Pointmap,
     shapes::{path::Path, path_style::PathStyle, point::Point, rectangle::Rectangle, shape::Shape},
     svg::document::Document,
@@ -8,6 +11,8 @@ use rand::Rng;
 
 fn main() {
     let (bg, palette) = palettes::Palettes::orange_autumn();
+    let color_map =
+        ColorMap::continuous(palette.as_ref(), PaletteOrder::Lightness).domain(-1.0, 1.0);
     let bounds = Rectangle::new(Point(0., 0.), 1000., 1000.0 * 1.4).set_color(bg);
     let mut doc = Document::new("swirl", bounds);
     let mut rng = rand::thread_rng();
@@ -17,19 +22,24 @@ fn main() {
     doc.add_shape(Box::new(bounds));
 
     for _ in 0..500 {
+        let mut x = rng.gen_range(bounds.x_range());
+        let mut y = rng.gen_range(bounds.y_range());
+        // Color every swirl by the noise where it starts, so neighboring swirls get similar colors.
         let style = PathStyle::new()
-            .stroke(palette.get_random_color().unwrap())
+            .stroke(
+                color_map
+                    .color_at(noise(x, y, &bounds.center()).sin())
+                    .unwrap(),
+            )
             .stroke_weight(10.0);
         let mut path = Path::new(vec![], style);
-        let mut x = rng.gen_range(bounds.x_range());
-        let mut y = rng.gen_range(bounds.y_range());
 
         while bounds.contains(&Point(x, y)) && path.length() < 400. {
             let point = Point(x, y);
 
             let neighbors = pointmap.get_neighbors(&point, Some(20.));
 
-            if neighbors.len() > 0 {
+            if !neighbors.is_empty() {
                 break;
             }
 
@@ -43,11 +53,12 @@ fn main() {
             continue;
         }
 
-        for point in &path.points {
+        for point in path.points() {
             let _ = pointmap.add_point(*point);
         }
 
-        doc.add_shape(Box::new(path));
+        // Every integration step is a point, most of them are not needed to draw the swirl.
+        doc.add_shape(Box::new(path.simplify(0.25)));
     }
 
     doc.save();
@@ -12,6 +12,7 @@
 
 pub mod palette;
 pub mod pointmap;
+pub mod raster;
 pub mod shapes;
 pub mod svg;
 pub mod util;
@@ -1,7 +1,9 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
+
+use super::{named_colors::named_color, pigment};
 
 /// Color representation
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Color {
     /**
     HSLa representation of a color. Good for when you want to do small variations to a given color.
@@ -16,7 +18,12 @@ pub enum Color {
     HSLa(u16, f64, f64, f64),
 
     /**
-    Hex representation for a color.
+    Hex representation for a color, as `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`. The
+    string is not checked until the color is converted, and the infallible conversions such
+    as [`Color::to_hex`] panic if it is not a valid hex color. Colors that are only known at
+    runtime should be parsed with [`str::parse`] instead, which returns an error for invalid
+    input and a [`Color::Rgba`] otherwise, or converted with the `try_` conversions such as
+    [`Color::try_to_hex`].
 
     Example:
 
@@ -26,6 +33,548 @@ pub enum Color {
     ```
     */
     Hex(&'static str),
+
+    /**
+    RGBa representation of a color, red, green and blue are in the range `0..=255` and
+    alpha is in the range `0..=1`. Channels outside of the sRGB gamut are kept as they are
+    so that conversions between color spaces stay lossless, they are only clamped when
+    the color is written out.
+
+    Example:
+
+    ```
+    use art::palette::color::Color::Rgba;
+    let bright_red = Rgba(255.0, 0.0, 0.0, 1.0);
+    ```
+    */
+    Rgba(f64, f64, f64, f64),
+
+    /**
+    [OKLab](https://bottosson.github.io/posts/oklab/) representation of a color. Lightness is
+    in the range `0..=1`, `a` and `b` are roughly in the range `-0.4..=0.4`, alpha is in the
+    range `0..=1`. Good for when you want to blend colors or measure how far apart they are.
+
+    Example:
+
+    ```
+    use art::palette::color::Color::Oklab;
+    let bright_red = Oklab(0.628, 0.225, 0.126, 1.0);
+    ```
+    */
+    Oklab(f64, f64, f64, f64),
+
+    /**
+    OKLCH representation of a color, the polar form of [`Color::Oklab`]. Lightness is in the
+    range `0..=1`, chroma is roughly in the range `0..=0.4`, hue is in degrees and alpha is in
+    the range `0..=1`. Good for when you want to design palettes with an even perceived
+    lightness.
+
+    Example:
+
+    ```
+    use art::palette::color::Color::Oklch;
+    let bright_red = Oklch(0.628, 0.258, 29.2, 1.0);
+    ```
+    */
+    Oklch(f64, f64, f64, f64),
+}
+
+impl Color {
+    /**
+    Convert this color to [`Color::Rgba`]. Panics if this is a [`Color::Hex`] that does
+    not hold a valid hex color, use [`Color::try_to_rgba`] for colors that are not known
+    to be valid.
+
+    Example:
+
+    ```
+    use art::palette::color::Color;
+    let red = Color::HSLa(0, 100.0, 50.0, 0.5).to_rgba();
+    assert_eq!(red, Color::Rgba(255.0, 0.0, 0.0, 0.5));
+    ```
+    */
+    pub fn to_rgba(&self) -> Color {
+        if let Color::Rgba(..) = self {
+            return *self;
+        }
+
+        let (r, g, b, a) = self.srgba();
+        Color::Rgba(r * 255.0, g * 255.0, b * 255.0, a)
+    }
+
+    /// Convert this color to [`Color::Rgba`], or an error if this is a [`Color::Hex`] that
+    /// does not hold a valid hex color.
+    pub fn try_to_rgba(&self) -> Result<Color, ParseColorError> {
+        let (r, g, b, a) = self.try_srgba()?;
+        Ok(Color::Rgba(r * 255.0, g * 255.0, b * 255.0, a))
+    }
+
+    /// Convert this color to [`Color::HSLa`]. The hue is rounded to whole degrees.
+    pub fn to_hsla(&self) -> Color {
+        if let Color::HSLa(..) = self {
+            return *self;
+        }
+
+        let (h, s, l, a) = self.hsla();
+        Color::HSLa(h.round() as u16 % 360, s, l, a)
+    }
+
+    /// Convert this color to [`Color::Oklab`].
+    pub fn to_oklab(&self) -> Color {
+        let (l, a, b, alpha) = self.oklab();
+        Color::Oklab(l, a, b, alpha)
+    }
+
+    /// Convert this color to [`Color::Oklch`].
+    pub fn to_oklch(&self) -> Color {
+        match *self {
+            Color::Oklch(..) => *self,
+            _ => {
+                let (l, a, b, alpha) = self.oklab();
+                let c = a.hypot(b);
+                let h = if c < 1e-9 {
+                    0.0
+                } else {
+                    b.atan2(a).to_degrees().rem_euclid(360.0)
+                };
+
+                Color::Oklch(l, c, h, alpha)
+            }
+        }
+    }
+
+    /**
+    Hex string for this color, as `#rrggbb` or `#rrggbbaa` if the color is translucent.
+    [`Color::Hex`] only holds static strings, so this is how to get from any other
+    representation to a hex value. Panics if this is a [`Color::Hex`] that does not hold a
+    valid hex color, use [`Color::try_to_hex`] for colors that are not known to be valid.
+
+    Example:
+
+    ```
+    use art::palette::color::Color;
+    assert_eq!(Color::Rgba(255.0, 0.0, 0.0, 1.0).to_hex(), "#ff0000");
+    ```
+    */
+    pub fn to_hex(&self) -> String {
+        self.try_to_hex().unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Hex string for this color like [`Color::to_hex`], or an error if this is a
+    /// [`Color::Hex`] that does not hold a valid hex color.
+    pub fn try_to_hex(&self) -> Result<String, ParseColorError> {
+        let (r, g, b, a) = self.try_srgba()?;
+        let [r, g, b, a] = to_bytes([r, g, b, a]);
+
+        Ok(if a == 255 {
+            format!("#{r:02x}{g:02x}{b:02x}")
+        } else {
+            format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+        })
+    }
+
+    /// The alpha channel of this color in the range `0..=1`.
+    pub fn alpha(&self) -> f64 {
+        self.srgba().3
+    }
+
+    /**
+    Make this color lighter by adding `amount` percentage points to its HSL lightness.
+
+    All the color manipulation methods return a new color in the same representation as
+    this color, except for [`Color::Hex`] which becomes [`Color::Rgba`].
+
+    Example:
+
+    ```
+    use art::palette::color::Color;
+    let base = Color::HSLa(37, 80.0, 55.0, 1.0);
+    assert_eq!(base.lighten(10.0), Color::HSLa(37, 80.0, 65.0, 1.0));
+    ```
+    */
+    pub fn lighten(&self, amount: f64) -> Color {
+        self.map_hsla(|h, s, l, a| (h, s, l + amount, a))
+    }
+
+    /// Make this color darker by subtracting `amount` percentage points from its HSL lightness.
+    pub fn darken(&self, amount: f64) -> Color {
+        self.lighten(-amount)
+    }
+
+    /// Make this color more vivid by adding `amount` percentage points to its HSL saturation.
+    pub fn saturate(&self, amount: f64) -> Color {
+        self.map_hsla(|h, s, l, a| (h, s + amount, l, a))
+    }
+
+    /// Make this color greyer by subtracting `amount` percentage points from its HSL saturation.
+    pub fn desaturate(&self, amount: f64) -> Color {
+        self.saturate(-amount)
+    }
+
+    /// Rotate the hue of this color by `degrees` around the color wheel.
+    pub fn rotate_hue(&self, degrees: f64) -> Color {
+        self.map_hsla(|h, s, l, a| (h + degrees, s, l, a))
+    }
+
+    /// The color on the opposite side of the color wheel.
+    pub fn complement(&self) -> Color {
+        self.rotate_hue(180.0)
+    }
+
+    /**
+    The same color with the alpha channel set to `alpha`, clamped to `0..=1`.
+
+    Example:
+
+    ```
+    use art::palette::color::Color;
+    let stroke = Color::Hex("#E1B31E").with_alpha(0.1);
+    assert_eq!(stroke, Color::Rgba(225.0, 179.0, 30.0, 0.1));
+    ```
+    */
+    pub fn with_alpha(&self, alpha: f64) -> Color {
+        let alpha = alpha.clamp(0.0, 1.0);
+
+        match self.to_rgba_if_hex() {
+            Color::HSLa(h, s, l, _) => Color::HSLa(h, s, l, alpha),
+            Color::Rgba(r, g, b, _) => Color::Rgba(r, g, b, alpha),
+            Color::Oklab(l, a, b, _) => Color::Oklab(l, a, b, alpha),
+            Color::Oklch(l, c, h, _) => Color::Oklch(l, c, h, alpha),
+            Color::Hex(_) => unreachable!(),
+        }
+    }
+
+    /**
+    Mix this color with `other` in OKLab, where `t = 0` is this color and `t = 1` is `other`.
+
+    Example:
+
+    ```
+    use art::palette::color::Color;
+    let red = Color::Hex("#ff0000");
+    let halfway = red.mix(&Color::Hex("#0000ff"), 0.5);
+    ```
+    */
+    pub fn mix(&self, other: &Color, t: f64) -> Color {
+        self.mix_in(other, t, ColorSpace::Oklab)
+    }
+
+    /**
+    The perceived difference between this color and `other` according to
+    [CIEDE2000](https://en.wikipedia.org/wiki/Color_difference#CIEDE2000). A difference
+    of about `1` is the smallest a trained eye can see, while `2` to `3` is noticeable at
+    a glance. Alpha is not taken into account.
+
+    Example:
+
+    ```
+    use art::palette::color::Color;
+    let difference = Color::Hex("#E1B31E").delta_e_2000(&Color::Hex("#E1B31F"));
+    assert!(difference < 1.0);
+    ```
+    */
+    pub fn delta_e_2000(&self, other: &Color) -> f64 {
+        ciede2000(self.lab(), other.lab())
+    }
+
+    /// The euclidean distance between this color and `other` in OKLab. This is much cheaper
+    /// than [`Color::delta_e_2000`] and almost as good, a difference of about `0.02` is just
+    /// noticeable. Alpha is not taken into account.
+    pub fn delta_e_ok(&self, other: &Color) -> f64 {
+        let (l1, a1, b1, _) = self.oklab();
+        let (l2, a2, b2, _) = other.oklab();
+
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// The relative luminance of this color as defined by
+    /// [WCAG](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance), `0` for black and `1`
+    /// for white.
+    pub fn relative_luminance(&self) -> f64 {
+        let (r, g, b, _) = self.srgba();
+        let [r, g, b] = [r, g, b].map(|channel| srgb_to_linear(channel.clamp(0.0, 1.0)));
+
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /**
+    The [WCAG contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) between this
+    color and `other`, from `1` for identical luminance up to `21` for black on white.
+
+    Example:
+
+    ```
+    use art::palette::color::Color;
+    let contrast = Color::Hex("#000").contrast_ratio(&Color::Hex("#fff"));
+    assert_eq!(contrast.round(), 21.0);
+    ```
+    */
+    pub fn contrast_ratio(&self, other: &Color) -> f64 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Mix this color with `other` in the given color space, where `t = 0` is this color
+    /// and `t = 1` is `other`.
+    pub fn mix_in(&self, other: &Color, t: f64, space: ColorSpace) -> Color {
+        self.same_representation(self.interpolate(other, t.clamp(0.0, 1.0), space))
+    }
+
+    /**
+    Mix this color with `other` like paint, where `t` is the share of `other`. Unlike
+    [`Color::mix`], which blends light, blue and yellow make green and mixes get darker
+    and stay saturated rather than turning grey. Alpha is mixed linearly.
+
+    Example:
+
+    ```
+    use art::palette::color::Color;
+    let blue = Color::Hex("#0000ff");
+    let green = blue.mix_pigment(&Color::Hex("#ffff00"), 0.5);
+    ```
+    */
+    pub fn mix_pigment(&self, other: &Color, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let (r1, g1, b1, a1) = self.srgba();
+        let (r2, g2, b2, a2) = other.srgba();
+        let [r, g, b] = pigment::mix([r1, g1, b1], [r2, g2, b2], t);
+
+        self.same_representation(Color::Rgba(
+            r * 255.0,
+            g * 255.0,
+            b * 255.0,
+            a1 + (a2 - a1) * t,
+        ))
+    }
+
+    /// Adjust this color in HSL, with the hue in degrees and saturation and lightness in percent,
+    /// keeping the representation of this color.
+    pub(crate) fn map_hsla(
+        &self,
+        adjust: impl FnOnce(f64, f64, f64, f64) -> (f64, f64, f64, f64),
+    ) -> Color {
+        let (h, s, l, a) = self.hsla();
+        let (h, s, l, a) = adjust(h, s, l, a);
+        let (h, s, l, a) = (
+            h.rem_euclid(360.0),
+            s.clamp(0.0, 100.0),
+            l.clamp(0.0, 100.0),
+            a.clamp(0.0, 1.0),
+        );
+
+        match self {
+            Color::HSLa(..) => Color::HSLa(h.round() as u16 % 360, s, l, a),
+            _ => {
+                let (r, g, b) = hsl_to_srgb(h, s, l);
+                self.same_representation(Color::Rgba(r * 255.0, g * 255.0, b * 255.0, a))
+            }
+        }
+    }
+
+    /// Convert `color` to the same representation as this color.
+    pub(crate) fn same_representation(&self, color: Color) -> Color {
+        match self {
+            Color::HSLa(..) => color.to_hsla(),
+            Color::Oklab(..) => color.to_oklab(),
+            Color::Oklch(..) => color.to_oklch(),
+            Color::Rgba(..) | Color::Hex(..) => color.to_rgba(),
+        }
+    }
+
+    fn to_rgba_if_hex(self) -> Color {
+        match self {
+            Color::Hex(_) => self.to_rgba(),
+            _ => self,
+        }
+    }
+
+    /**
+    Gamma encoded sRGB channels and alpha, all in the range `0..=1` for in-gamut colors.
+    This is the only conversion that can fail, when a [`Color::Hex`] does not hold a valid
+    hex color.
+
+    Example:
+
+    ```
+    use art::palette::color::{Color, ParseColorError};
+
+    assert_eq!(Color::Hex("#f00").try_srgba(), Ok((1.0, 0.0, 0.0, 1.0)));
+    assert_eq!(
+        Color::Hex("red").try_srgba(),
+        Err(ParseColorError::InvalidHex(String::from("red")))
+    );
+    ```
+    */
+    pub fn try_srgba(&self) -> Result<(f64, f64, f64, f64), ParseColorError> {
+        Ok(match *self {
+            Color::Hex(color) => parse_hex(color.trim_start_matches('#'))
+                .ok_or_else(|| ParseColorError::InvalidHex(color.to_string()))?,
+            Color::Rgba(r, g, b, a) => (r / 255.0, g / 255.0, b / 255.0, a),
+            Color::HSLa(h, s, l, a) => {
+                let (r, g, b) = hsl_to_srgb(h as f64, s, l);
+                (r, g, b, a)
+            }
+            Color::Oklab(..) | Color::Oklch(..) => {
+                let (l, a, b, alpha) = self.oklab();
+                let (r, g, b) = oklab_to_linear(l, a, b);
+
+                (
+                    linear_to_srgb(r),
+                    linear_to_srgb(g),
+                    linear_to_srgb(b),
+                    alpha,
+                )
+            }
+        })
+    }
+
+    /// [`Color::try_srgba`] for the infallible conversions, panics if this is a
+    /// [`Color::Hex`] that does not hold a valid hex color.
+    pub(crate) fn srgba(&self) -> (f64, f64, f64, f64) {
+        self.try_srgba().unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Hue in degrees, saturation and lightness in percent, and alpha.
+    pub(crate) fn hsla(&self) -> (f64, f64, f64, f64) {
+        if let Color::HSLa(h, s, l, a) = *self {
+            return (h as f64, s, l, a);
+        }
+
+        let (r, g, b, a) = self.srgba();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+
+        if d == 0.0 {
+            return (0.0, 0.0, l * 100.0, a);
+        }
+
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            ((g - b) / d).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+
+        (h * 60.0, s * 100.0, l * 100.0, a)
+    }
+
+    /// Interpolate between this color and `other` in the given color space, where `t = 0`
+    /// is this color and `t = 1` is `other`. Hues take the shortest way around the color
+    /// wheel.
+    pub(crate) fn interpolate(&self, other: &Color, t: f64, space: ColorSpace) -> Color {
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+
+        match space {
+            ColorSpace::Srgb => {
+                let (r1, g1, b1, a1) = self.srgba();
+                let (r2, g2, b2, a2) = other.srgba();
+
+                Color::Rgba(
+                    lerp(r1, r2) * 255.0,
+                    lerp(g1, g2) * 255.0,
+                    lerp(b1, b2) * 255.0,
+                    lerp(a1, a2),
+                )
+            }
+            ColorSpace::LinearRgb => {
+                let (r1, g1, b1, a1) = self.srgba();
+                let (r2, g2, b2, a2) = other.srgba();
+                let channel = |a: f64, b: f64| {
+                    linear_to_srgb(lerp(srgb_to_linear(a), srgb_to_linear(b))) * 255.0
+                };
+
+                Color::Rgba(
+                    channel(r1, r2),
+                    channel(g1, g2),
+                    channel(b1, b2),
+                    lerp(a1, a2),
+                )
+            }
+            ColorSpace::Hsl => {
+                let (h1, s1, l1, a1) = self.hsla();
+                let (h2, s2, l2, a2) = other.hsla();
+                let (h1, h2) = powerless_hues(h1, s1 == 0.0, h2, s2 == 0.0);
+
+                Color::HSLa(
+                    lerp_hue(h1, h2, t).round() as u16 % 360,
+                    lerp(s1, s2),
+                    lerp(l1, l2),
+                    lerp(a1, a2),
+                )
+            }
+            ColorSpace::Oklab => {
+                let (l1, a1, b1, alpha1) = self.oklab();
+                let (l2, a2, b2, alpha2) = other.oklab();
+
+                Color::Oklab(
+                    lerp(l1, l2),
+                    lerp(a1, a2),
+                    lerp(b1, b2),
+                    lerp(alpha1, alpha2),
+                )
+            }
+            ColorSpace::Oklch => {
+                let Color::Oklch(l1, c1, h1, a1) = self.to_oklch() else {
+                    unreachable!()
+                };
+                let Color::Oklch(l2, c2, h2, a2) = other.to_oklch() else {
+                    unreachable!()
+                };
+                let (h1, h2) = powerless_hues(h1, c1 < 1e-4, h2, c2 < 1e-4);
+
+                Color::Oklch(
+                    lerp(l1, l2),
+                    lerp(c1, c2),
+                    lerp_hue(h1, h2, t),
+                    lerp(a1, a2),
+                )
+            }
+        }
+    }
+
+    /// CIE L*a*b* under the D65 white point.
+    pub(crate) fn lab(&self) -> (f64, f64, f64) {
+        let (r, g, b, _) = self.srgba();
+        let (r, g, b) = (srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b));
+
+        let x = (0.4124564 * r + 0.3575761 * g + 0.1804375 * b) / 0.95047;
+        let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+        let z = (0.0193339 * r + 0.1191920 * g + 0.9503041 * b) / 1.08883;
+
+        let f = |t: f64| {
+            if t > 216.0 / 24389.0 {
+                t.cbrt()
+            } else {
+                (24389.0 / 27.0 * t + 16.0) / 116.0
+            }
+        };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+
+        (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+
+    /// OKLab lightness, a, b and alpha.
+    pub(crate) fn oklab(&self) -> (f64, f64, f64, f64) {
+        match *self {
+            Color::Oklab(l, a, b, alpha) => (l, a, b, alpha),
+            Color::Oklch(l, c, h, alpha) => {
+                let h = h.to_radians();
+                (l, c * h.cos(), c * h.sin(), alpha)
+            }
+            _ => {
+                let (r, g, b, alpha) = self.srgba();
+                let (l, a, b) =
+                    linear_to_oklab(srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b));
+
+                (l, a, b, alpha)
+            }
+        }
+    }
 }
 
 impl Display for Color {
@@ -35,43 +584,359 @@ impl Display for Color {
             Color::HSLa(h, s, l, a) => {
                 write!(f, "hsla({h}, {s:.1}%, {l:.1}%, {a:.2})")
             }
+            Color::Rgba(r, g, b, a) => {
+                let [r, g, b] = [r, g, b].map(|channel| channel.clamp(0.0, 255.0));
+                write!(f, "rgba({r:.0}, {g:.0}, {b:.0}, {a:.2})")
+            }
+            Color::Oklab(l, a, b, alpha) => {
+                write!(f, "oklab({:.2}% {a:.4} {b:.4} / {alpha:.2})", l * 100.0)
+            }
+            Color::Oklch(l, c, h, alpha) => {
+                write!(f, "oklch({:.2}% {c:.4} {h:.2} / {alpha:.2})", l * 100.0)
+            }
         }
     }
 }
 
-impl Into<image::Rgb<u8>> for Color {
-    fn into(self) -> image::Rgb<u8> {
+/// Panics if the color is a [`Color::Hex`] that does not hold a valid hex color, see
+/// [`Color::try_srgba`].
+impl From<Color> for image::Rgb<u8> {
+    fn from(color: Color) -> Self {
+        let [r, g, b, _] = image::Rgba::<u8>::from(color).0;
+        image::Rgb([r, g, b])
+    }
+}
+
+/// Panics if the color is a [`Color::Hex`] that does not hold a valid hex color, see
+/// [`Color::try_srgba`].
+impl From<Color> for image::Rgba<u8> {
+    fn from(color: Color) -> Self {
+        let (r, g, b, a) = color.srgba();
+        image::Rgba(to_bytes([r, g, b, a]))
+    }
+}
+
+/// Channels in the range `0..=1` as bytes, clamping colors outside of the sRGB gamut.
+fn to_bytes(channels: [f64; 4]) -> [u8; 4] {
+    channels.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+impl From<image::Rgb<u8>> for Color {
+    fn from(pixel: image::Rgb<u8>) -> Self {
+        let [r, g, b] = pixel.0;
+        Color::Rgba(r as f64, g as f64, b as f64, 1.0)
+    }
+}
+
+impl From<image::Rgba<u8>> for Color {
+    fn from(pixel: image::Rgba<u8>) -> Self {
+        let [r, g, b, a] = pixel.0;
+        Color::Rgba(r as f64, g as f64, b as f64, a as f64 / 255.0)
+    }
+}
+
+/// The ways the difference between two colors can be measured.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDifference {
+    /// [`Color::delta_e_2000`], the most accurate.
+    #[default]
+    Ciede2000,
+
+    /// [`Color::delta_e_ok`], faster, with much smaller values.
+    Oklab,
+}
+
+impl ColorDifference {
+    /// The difference between `a` and `b` using this metric.
+    pub fn between(&self, a: &Color, b: &Color) -> f64 {
         match self {
-            Color::Hex(color) => {
-                let color = color.trim_start_matches('#');
-                let r = u8::from_str_radix(&color[0..2], 16).unwrap();
-                let g = u8::from_str_radix(&color[2..4], 16).unwrap();
-                let b = u8::from_str_radix(&color[4..6], 16).unwrap();
+            ColorDifference::Ciede2000 => a.delta_e_2000(b),
+            ColorDifference::Oklab => a.delta_e_ok(b),
+        }
+    }
+}
 
-                image::Rgb([r, g, b])
-            }
-            Color::HSLa(h, s, l, _) => {
-                let h = h as f64 / 360.0;
-                let s = s / 100.0;
-                let l = l / 100.0;
+/// CIEDE2000 color difference between two CIE L*a*b* colors.
+fn ciede2000(lab1: (f64, f64, f64), lab2: (f64, f64, f64)) -> f64 {
+    let (l1, a1, b1) = lab1;
+    let (l2, a2, b2) = lab2;
 
-                let q = if l < 0.5 {
-                    l * (1.0 + s)
-                } else {
-                    l + s - l * s
-                };
-                let p = 2.0 * l - q;
+    let c_mean = (a1.hypot(b1) + a2.hypot(b2)) / 2.0;
+    let g = 0.5 * (1.0 - (c_mean.powi(7) / (c_mean.powi(7) + 25f64.powi(7))).sqrt());
+    let (a1, a2) = (a1 * (1.0 + g), a2 * (1.0 + g));
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+
+    let hue = |a: f64, b: f64| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
 
-                let r = hue_to_rgb(p, q, h + 1.0 / 3.0);
-                let g = hue_to_rgb(p, q, h);
-                let b = hue_to_rgb(p, q, h - 1.0 / 3.0);
+    let l_mean = (l1 + l2) / 2.0;
+    let c_mean = (c1 + c2) / 2.0;
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_mean).to_radians().cos()
+        + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+
+    let s_l = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_mean;
+    let s_h = 1.0 + 0.015 * c_mean * t;
+
+    let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_mean.powi(7) / (c_mean.powi(7) + 25f64.powi(7))).sqrt();
+    let r_t = -r_c * (2.0 * delta_theta).to_radians().sin();
+
+    ((delta_l / s_l).powi(2)
+        + (delta_c / s_c).powi(2)
+        + (delta_h / s_h).powi(2)
+        + r_t * (delta_c / s_c) * (delta_h / s_h))
+        .sqrt()
+}
 
-                image::Rgb([(r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8])
+/// The color spaces that colors can be blended in. Blending in [`ColorSpace::Oklab`] gives
+/// even, perceptually smooth transitions, while [`ColorSpace::Srgb`] matches what most other
+/// tools do and tends to go muddy or dark between saturated colors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Gamma encoded sRGB, the way colors are written in hex.
+    Srgb,
+
+    /// sRGB without gamma encoding, physically correct light mixing.
+    LinearRgb,
+
+    /// Hue, saturation and lightness, the hue goes around the color wheel.
+    Hsl,
+
+    /// Perceptual OKLab.
+    #[default]
+    Oklab,
+
+    /// Polar OKLab, the hue goes around the color wheel.
+    Oklch,
+}
+
+/// A hue is meaningless for grey colors, so take the hue of the other color to avoid
+/// sweeping through unrelated hues on the way.
+fn powerless_hues(h1: f64, h1_powerless: bool, h2: f64, h2_powerless: bool) -> (f64, f64) {
+    match (h1_powerless, h2_powerless) {
+        (true, false) => (h2, h2),
+        (false, true) => (h1, h1),
+        _ => (h1, h2),
+    }
+}
+
+/// Interpolate between two hues in degrees the shortest way around the color wheel.
+fn lerp_hue(from: f64, to: f64, t: f64) -> f64 {
+    let delta = (to - from + 540.0).rem_euclid(360.0) - 180.0;
+    (from + delta * t).rem_euclid(360.0)
+}
+
+/// Error returned when a string can not be parsed into a [`Color`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The string starts with `#` but is not 3, 4, 6 or 8 hex digits.
+    InvalidHex(String),
+
+    /// The string is a color function, such as `rgb()` or `hsl()`, with the wrong number
+    /// of arguments or with an argument that is not a number.
+    InvalidFunction(String),
+
+    /// The string is not a hex color, a color function or a named color.
+    UnknownColor(String),
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseColorError::InvalidHex(input) => write!(f, "invalid hex color \"{input}\""),
+            ParseColorError::InvalidFunction(input) => {
+                write!(f, "invalid color function \"{input}\"")
             }
+            ParseColorError::UnknownColor(input) => write!(f, "unknown color \"{input}\""),
         }
     }
 }
 
+impl std::error::Error for ParseColorError {}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /**
+    Parse a CSS color: `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, `rgb()`, `rgba()`, `hsl()`,
+    `hsla()`, `oklab()`, `oklch()` or one of the CSS named colors. Color functions accept
+    both the comma separated and the space separated syntax.
+
+    Example:
+
+    ```
+    use art::palette::color::Color;
+
+    let red: Color = "#f00".parse().unwrap();
+    let translucent_red: Color = "rgba(255, 0, 0, 0.5)".parse().unwrap();
+    let green: Color = "hsl(120 100% 50%)".parse().unwrap();
+    let blue: Color = "RoyalBlue".parse().unwrap();
+
+    assert!("#ff00z0".parse::<Color>().is_err());
+    ```
+    */
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim();
+
+        if let Some(hex) = input.strip_prefix('#') {
+            let (r, g, b, a) =
+                parse_hex(hex).ok_or_else(|| ParseColorError::InvalidHex(input.to_string()))?;
+
+            return Ok(Color::Rgba(r * 255.0, g * 255.0, b * 255.0, a));
+        }
+
+        if input.contains('(') {
+            return parse_function(input)
+                .ok_or_else(|| ParseColorError::InvalidFunction(input.to_string()));
+        }
+
+        if input.eq_ignore_ascii_case("transparent") {
+            return Ok(Color::Rgba(0.0, 0.0, 0.0, 0.0));
+        }
+
+        named_color(input)
+            .map(|rgb| {
+                let [_, r, g, b] = rgb.to_be_bytes();
+                Color::Rgba(r as f64, g as f64, b as f64, 1.0)
+            })
+            .ok_or_else(|| ParseColorError::UnknownColor(input.to_string()))
+    }
+}
+
+/// Parse 3, 4, 6 or 8 hex digits, without the leading `#`, into sRGB channels and alpha.
+fn parse_hex(hex: &str) -> Option<(f64, f64, f64, f64)> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let digits = match hex.len() {
+        3 | 4 => hex.chars().flat_map(|c| [c, c]).collect::<String>(),
+        6 | 8 => hex.to_string(),
+        _ => return None,
+    };
+
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    let alpha = match digits.len() {
+        8 => channel(6)?,
+        _ => 255,
+    };
+
+    Some((
+        channel(0)? as f64 / 255.0,
+        channel(2)? as f64 / 255.0,
+        channel(4)? as f64 / 255.0,
+        alpha as f64 / 255.0,
+    ))
+}
+
+/// Parse a CSS color function such as `rgba(255, 0, 0, 0.5)` or `oklch(62% 0.25 29 / 50%)`.
+fn parse_function(input: &str) -> Option<Color> {
+    let (name, args) = input.strip_suffix(')')?.split_once('(')?;
+    let args = args
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|arg| !arg.is_empty())
+        .collect::<Vec<&str>>();
+
+    let alpha = match args.len() {
+        3 => 1.0,
+        4 => percentage_or_number(args[3], 1.0)?,
+        _ => return None,
+    };
+
+    match name.trim().to_ascii_lowercase().as_str() {
+        "rgb" | "rgba" => Some(Color::Rgba(
+            percentage_or_number(args[0], 255.0)?,
+            percentage_or_number(args[1], 255.0)?,
+            percentage_or_number(args[2], 255.0)?,
+            alpha,
+        )),
+        "hsl" | "hsla" => Some(Color::HSLa(
+            parse_hue(args[0])?.rem_euclid(360.0).round() as u16 % 360,
+            args[1].trim_end_matches('%').parse().ok()?,
+            args[2].trim_end_matches('%').parse().ok()?,
+            alpha,
+        )),
+        "oklab" => Some(Color::Oklab(
+            percentage_or_number(args[0], 1.0)?,
+            percentage_or_number(args[1], 0.4)?,
+            percentage_or_number(args[2], 0.4)?,
+            alpha,
+        )),
+        "oklch" => Some(Color::Oklch(
+            percentage_or_number(args[0], 1.0)?,
+            percentage_or_number(args[1], 0.4)?,
+            parse_hue(args[2])?,
+            alpha,
+        )),
+        _ => None,
+    }
+}
+
+/// Parse a plain number, or a percentage where 100% equals `full`.
+fn percentage_or_number(arg: &str, full: f64) -> Option<f64> {
+    match arg.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f64>().ok().map(|p| p / 100.0 * full),
+        None => arg.parse().ok(),
+    }
+}
+
+/// Parse a hue in degrees, with or without the `deg` unit.
+fn parse_hue(arg: &str) -> Option<f64> {
+    arg.trim_end_matches("deg").parse().ok()
+}
+
+/// Hue in degrees, saturation and lightness in percent to sRGB channels in `0..=1`.
+fn hsl_to_srgb(h: f64, s: f64, l: f64) -> (f64, f64, f64) {
+    let h = h.rem_euclid(360.0) / 360.0;
+    let s = s / 100.0;
+    let l = l / 100.0;
+
+    let q = if l < 0.5 {
+        l * (1.0 + s)
+    } else {
+        l + s - l * s
+    };
+    let p = 2.0 * l - q;
+
+    let r = hue_to_rgb(p, q, h + 1.0 / 3.0);
+    let g = hue_to_rgb(p, q, h);
+    let b = hue_to_rgb(p, q, h - 1.0 / 3.0);
+
+    (r, g, b)
+}
+
 fn hue_to_rgb(p: f64, q: f64, h: f64) -> f64 {
     let h = if h < 0.0 {
         h + 1.0
@@ -92,11 +957,61 @@ fn hue_to_rgb(p: f64, q: f64, h: f64) -> f64 {
     }
 }
 
+pub(crate) fn srgb_to_linear(channel: f64) -> f64 {
+    if channel.abs() <= 0.04045 {
+        channel / 12.92
+    } else {
+        channel.signum() * ((channel.abs() + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub(crate) fn linear_to_srgb(channel: f64) -> f64 {
+    if channel.abs() <= 0.0031308 {
+        channel * 12.92
+    } else {
+        channel.signum() * (1.055 * channel.abs().powf(1.0 / 2.4) - 0.055)
+    }
+}
+
+pub(crate) fn linear_to_oklab(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let l = 0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b;
+    let m = 0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b;
+    let s = 0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b;
+
+    let (l, m, s) = (l.cbrt(), m.cbrt(), s.cbrt());
+
+    (
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    )
+}
+
+pub(crate) fn oklab_to_linear(l: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    let l_ = l + 0.3963377774 * a + 0.2158037573 * b;
+    let m_ = l - 0.1055613458 * a - 0.0638541728 * b;
+    let s_ = l - 0.0894841775 * a - 1.2914855480 * b;
+
+    let (l, m, s) = (l_.powi(3), m_.powi(3), s_.powi(3));
+
+    (
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    )
+}
+
 #[cfg(test)]
 mod test {
-    use image::Rgb;
+    use image::{Rgb, Rgba};
+
+    use super::{Color, ColorSpace, ParseColorError};
 
-    use super::Color;
+    fn assert_close(a: Color, b: Color) {
+        let (a, b) = (a.srgba(), b.srgba());
+        let diff = (a.0 - b.0).abs() + (a.1 - b.1).abs() + (a.2 - b.2).abs() + (a.3 - b.3).abs();
+        assert!(diff < 1e-6, "{a:?} != {b:?}");
+    }
 
     #[test]
     fn render_hsla() {
@@ -111,9 +1026,344 @@ mod test {
         assert_eq!("#111", color.to_string());
     }
 
+    #[test]
+    fn render_rgba() {
+        let color = Color::Rgba(255.0, 300.0, -4.0, 0.5);
+        assert_eq!("rgba(255, 255, 0, 0.50)", color.to_string());
+    }
+
+    #[test]
+    fn render_oklab_and_oklch() {
+        let red = Color::Hex("#ff0000");
+
+        assert_eq!(
+            "oklab(62.80% 0.2249 0.1258 / 1.00)",
+            red.to_oklab().to_string()
+        );
+        assert_eq!(
+            "oklch(62.80% 0.2577 29.23 / 1.00)",
+            red.to_oklch().to_string()
+        );
+    }
+
     #[test]
     fn into_rgb() {
         let rgb: Rgb<u8> = Color::Hex("#ff0000").into();
         assert_eq!(rgb, image::Rgb([255, 0, 0]));
     }
+
+    #[test]
+    fn into_rgb_shorthand() {
+        let rgb: Rgb<u8> = Color::Hex("#f00").into();
+        assert_eq!(rgb, image::Rgb([255, 0, 0]));
+
+        let rgba: Rgba<u8> = Color::Hex("#0f08").into();
+        assert_eq!(rgba, image::Rgba([0, 255, 0, 136]));
+    }
+
+    #[test]
+    fn into_rgba_keeps_alpha() {
+        let rgba: Rgba<u8> = Color::HSLa(120, 100.0, 50.0, 0.5).into();
+        assert_eq!(rgba, image::Rgba([0, 255, 0, 128]));
+    }
+
+    #[test]
+    fn round_trip_between_all_variants() {
+        let colors = [
+            Color::Hex("#3a7bd580"),
+            Color::Rgba(12.5, 200.0, 99.0, 0.3),
+            Color::HSLa(210, 40.0, 60.0, 0.8),
+            Color::Oklab(0.7, -0.1, 0.05, 1.0),
+            Color::Oklch(0.5, 0.12, 300.0, 0.25),
+        ];
+
+        for color in colors {
+            assert_close(color, color.to_rgba().to_oklab().to_oklch());
+            assert_close(color, color.to_oklch().to_oklab().to_rgba());
+        }
+    }
+
+    #[test]
+    fn to_hsla() {
+        assert_eq!(
+            Color::Rgba(255.0, 0.0, 0.0, 1.0).to_hsla(),
+            Color::HSLa(0, 100.0, 50.0, 1.0)
+        );
+        assert_eq!(
+            Color::Hex("#0000ff").to_hsla(),
+            Color::HSLa(240, 100.0, 50.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn to_hex() {
+        assert_eq!(Color::HSLa(0, 100.0, 50.0, 1.0).to_hex(), "#ff0000");
+        assert_eq!(Color::Rgba(0.0, 0.0, 255.0, 0.0).to_hex(), "#0000ff00");
+    }
+
+    #[test]
+    fn invalid_hex() {
+        let invalid = Color::Hex("#12345");
+        let error = ParseColorError::InvalidHex(String::from("#12345"));
+
+        assert_eq!(invalid.try_srgba(), Err(error.clone()));
+        assert_eq!(invalid.try_to_rgba(), Err(error.clone()));
+        assert_eq!(invalid.try_to_hex(), Err(error));
+        assert_eq!(
+            Color::Hex("#0f08").try_to_hex(),
+            Ok(String::from("#00ff0088"))
+        );
+    }
+
+    #[test]
+    fn parse_hex() {
+        assert_eq!("#f00".parse(), Ok(Color::Rgba(255.0, 0.0, 0.0, 1.0)));
+        assert_eq!("#ff000000".parse(), Ok(Color::Rgba(255.0, 0.0, 0.0, 0.0)));
+        assert_eq!(
+            " #00FF00 ".parse::<Color>().map(|color| color.to_hex()),
+            Ok(String::from("#00ff00"))
+        );
+    }
+
+    #[test]
+    fn parse_functions() {
+        assert_eq!(
+            "rgba(255, 0, 0, 0.5)".parse(),
+            Ok(Color::Rgba(255.0, 0.0, 0.0, 0.5))
+        );
+        assert_eq!(
+            "rgb(100% 0% 0% / 50%)".parse(),
+            Ok(Color::Rgba(255.0, 0.0, 0.0, 0.5))
+        );
+        assert_eq!(
+            "hsl(120deg, 100%, 50%)".parse(),
+            Ok(Color::HSLa(120, 100.0, 50.0, 1.0))
+        );
+        assert_eq!(
+            "HSLA(-90 10% 20% / 0.3)".parse(),
+            Ok(Color::HSLa(270, 10.0, 20.0, 0.3))
+        );
+        assert_eq!(
+            "oklch(62.8% 0.2577 29.23 / 1.00)".parse(),
+            Ok(Color::Oklch(0.628, 0.2577, 29.23, 1.0))
+        );
+    }
+
+    #[test]
+    fn parse_display_output() {
+        let colors = [
+            Color::Rgba(12.0, 200.0, 99.0, 0.3),
+            Color::HSLa(210, 40.0, 60.0, 0.8),
+            Color::Oklab(0.7, -0.1, 0.05, 1.0),
+            Color::Oklch(0.5, 0.12, 300.0, 0.25),
+        ];
+
+        for color in colors {
+            assert_eq!(color.to_string().parse(), Ok(color));
+        }
+    }
+
+    #[test]
+    fn parse_named() {
+        assert_eq!("red".parse(), Ok(Color::Rgba(255.0, 0.0, 0.0, 1.0)));
+        assert_eq!(
+            "RebeccaPurple".parse(),
+            Ok(Color::Rgba(102.0, 51.0, 153.0, 1.0))
+        );
+        assert_eq!("transparent".parse(), Ok(Color::Rgba(0.0, 0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "#ff000".parse::<Color>(),
+            Err(ParseColorError::InvalidHex(String::from("#ff000")))
+        );
+        assert_eq!(
+            "#gg0000".parse::<Color>(),
+            Err(ParseColorError::InvalidHex(String::from("#gg0000")))
+        );
+        assert_eq!(
+            "rgb(1, 2)".parse::<Color>(),
+            Err(ParseColorError::InvalidFunction(String::from("rgb(1, 2)")))
+        );
+        assert_eq!(
+            "rgb(1, 2, blue)".parse::<Color>(),
+            Err(ParseColorError::InvalidFunction(String::from(
+                "rgb(1, 2, blue)"
+            )))
+        );
+        assert_eq!(
+            "notacolor".parse::<Color>(),
+            Err(ParseColorError::UnknownColor(String::from("notacolor")))
+        );
+    }
+
+    #[test]
+    fn interpolate_endpoints() {
+        let red = Color::Hex("#ff0000");
+        let blue = Color::Hex("#0000ff");
+
+        for space in [
+            ColorSpace::Srgb,
+            ColorSpace::LinearRgb,
+            ColorSpace::Hsl,
+            ColorSpace::Oklab,
+            ColorSpace::Oklch,
+        ] {
+            assert_close(red.interpolate(&blue, 0.0, space), red);
+            assert_close(red.interpolate(&blue, 1.0, space), blue);
+        }
+    }
+
+    #[test]
+    fn interpolate_hue_takes_shortest_path() {
+        let a = Color::HSLa(350, 100.0, 50.0, 1.0);
+        let b = Color::HSLa(30, 100.0, 50.0, 1.0);
+
+        assert_eq!(
+            a.interpolate(&b, 0.5, ColorSpace::Hsl),
+            Color::HSLa(10, 100.0, 50.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn interpolate_from_grey_keeps_hue() {
+        let white = Color::HSLa(0, 0.0, 100.0, 1.0);
+        let blue = Color::HSLa(240, 100.0, 50.0, 1.0);
+
+        assert_eq!(
+            white.interpolate(&blue, 0.5, ColorSpace::Hsl),
+            Color::HSLa(240, 50.0, 75.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn lighten_and_darken() {
+        let base = Color::HSLa(37, 80.0, 55.0, 0.1);
+
+        assert_eq!(base.lighten(10.0), Color::HSLa(37, 80.0, 65.0, 0.1));
+        assert_eq!(base.darken(60.0), Color::HSLa(37, 80.0, 0.0, 0.1));
+        assert_close(
+            Color::Hex("#808080").lighten(50.0),
+            Color::Rgba(255.0, 255.0, 255.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn saturate_and_desaturate() {
+        let base = Color::HSLa(200, 50.0, 50.0, 1.0);
+
+        assert_eq!(base.saturate(20.0), Color::HSLa(200, 70.0, 50.0, 1.0));
+        assert_eq!(base.desaturate(80.0), Color::HSLa(200, 0.0, 50.0, 1.0));
+    }
+
+    #[test]
+    fn rotate_hue_and_complement() {
+        let red = Color::Hex("#ff0000");
+
+        assert_close(red.rotate_hue(120.0), Color::Hex("#00ff00"));
+        assert_close(red.rotate_hue(-120.0), Color::Hex("#0000ff"));
+        assert_close(red.complement(), Color::Hex("#00ffff"));
+        assert_eq!(
+            Color::HSLa(350, 10.0, 10.0, 1.0).rotate_hue(20.0),
+            Color::HSLa(10, 10.0, 10.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn manipulation_keeps_representation() {
+        let oklch = Color::Oklch(0.6, 0.1, 120.0, 1.0);
+
+        assert!(matches!(oklch.lighten(5.0), Color::Oklch(..)));
+        assert!(matches!(oklch.with_alpha(0.5), Color::Oklch(_, _, _, a) if a == 0.5));
+        assert!(matches!(Color::Hex("#fff").darken(5.0), Color::Rgba(..)));
+        assert!(matches!(
+            Color::Oklab(0.5, 0.0, 0.0, 1.0).mix(&Color::Hex("#f00"), 0.5),
+            Color::Oklab(..)
+        ));
+    }
+
+    #[test]
+    fn with_alpha() {
+        assert_eq!(
+            Color::Hex("#ff0000").with_alpha(0.5),
+            Color::Rgba(255.0, 0.0, 0.0, 0.5)
+        );
+        assert_eq!(
+            Color::HSLa(10, 10.0, 10.0, 1.0).with_alpha(2.0),
+            Color::HSLa(10, 10.0, 10.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn mix() {
+        let black = Color::Hex("#000000");
+        let white = Color::Hex("#ffffff");
+
+        assert_close(black.mix(&white, 0.0), black);
+        assert_close(black.mix(&white, 1.0), white);
+        assert_eq!(
+            black.mix_in(&white, 0.5, ColorSpace::Srgb).to_hex(),
+            "#808080"
+        );
+    }
+
+    #[test]
+    fn mix_pigment() {
+        let blue = Color::Hex("#0000ff");
+        let yellow = Color::Hex("#ffff00");
+
+        let Color::Oklch(_, chroma, hue, _) = blue.mix_pigment(&yellow, 0.5).to_oklch() else {
+            unreachable!()
+        };
+        let Color::Oklch(_, grey, ..) = blue.mix_in(&yellow, 0.5, ColorSpace::Srgb).to_oklch()
+        else {
+            unreachable!()
+        };
+        assert!((120.0..200.0).contains(&hue), "{hue}");
+        assert!(chroma > grey, "{chroma} <= {grey}");
+
+        let color = Color::HSLa(30, 60.0, 40.0, 0.4);
+        assert_eq!(color.mix_pigment(&color, 0.7).to_hex(), color.to_hex());
+        assert_eq!(
+            blue.with_alpha(0.0).mix_pigment(&yellow, 0.25).alpha(),
+            0.25
+        );
+    }
+
+    #[test]
+    fn ciede2000_reference_pairs() {
+        // Pairs from Sharma, Wu and Dalal, "The CIEDE2000 Color-Difference Formula".
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 3.1571, -77.2803), (50.0, 0.0, -82.7485), 2.8615),
+            ((50.0, 2.5, 0.0), (50.0, 0.0, -2.5), 4.3065),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            (
+                (60.2574, -34.0099, 36.2677),
+                (60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+            ((2.0776, 0.0795, -1.135), (0.9033, -0.0636, -0.5514), 0.9082),
+        ];
+
+        for (lab1, lab2, expected) in pairs {
+            assert!((super::ciede2000(lab1, lab2) - expected).abs() < 1e-4);
+            assert!((super::ciede2000(lab2, lab1) - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn delta_e() {
+        let red = Color::Hex("#ff0000");
+
+        assert_eq!(red.delta_e_2000(&red.to_oklch()).round(), 0.0);
+        assert_eq!(red.delta_e_ok(&Color::Rgba(255.0, 0.0, 0.0, 0.2)), 0.0);
+        assert!(red.delta_e_2000(&Color::Hex("#0000ff")) > 50.0);
+        assert!(
+            (Color::Hex("#000").delta_e_ok(&Color::Hex("#fff")) - 1.0).abs() < 1e-3,
+            "black and white are 1 apart in OKLab"
+        );
+    }
 }
@@ -0,0 +1,250 @@
+use rand::{Rng, RngCore};
+
+use super::{color::Color, gradient::Gradient, Palette};
+
+/// How the colors of a palette are ordered when it is turned into a [`ColorMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteOrder {
+    /// From dark to light, by OKLab lightness.
+    Lightness,
+
+    /// Around the color wheel, by OKLCH hue starting at red.
+    Hue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Mapping {
+    Continuous(Gradient),
+    Stepped(Vec<Color>),
+}
+
+/**
+Maps a scalar, such as a noise value, a distance or a density, to a color. Values are
+mapped from the domain of the map, `0..1` unless changed with [`ColorMap::domain`], and
+values outside of the domain get the color at the closest end.
+
+There are built-in perceptually uniform maps, and any palette can be turned into a map,
+either blending smoothly between its colors or in hard steps.
+
+Example
+
+```
+use art::palette::{
+    color_map::{ColorMap, PaletteOrder},
+    palettes::Palettes,
+};
+
+let viridis = ColorMap::viridis().domain(-1.0, 1.0);
+let color = viridis.color_at(0.25);
+
+let (_, palette) = Palettes::orange_autumn();
+let stepped = ColorMap::stepped(palette.as_ref(), PaletteOrder::Lightness);
+let darkest = stepped.color_at(0.0);
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorMap {
+    mapping: Mapping,
+    domain: (f64, f64),
+}
+
+impl ColorMap {
+    /// A map that blends smoothly between `colors`, spread out evenly from the start to the
+    /// end of the domain.
+    pub fn new(colors: Vec<Color>) -> ColorMap {
+        ColorMap {
+            mapping: Mapping::Continuous(Gradient::evenly_spaced(colors)),
+            domain: (0.0, 1.0),
+        }
+    }
+
+    /// A map that follows a gradient.
+    pub fn from_gradient(gradient: Gradient) -> ColorMap {
+        ColorMap {
+            mapping: Mapping::Continuous(gradient),
+            domain: (0.0, 1.0),
+        }
+    }
+
+    /// A map that blends smoothly between the colors of `palette`, sorted by `order`.
+    pub fn continuous(palette: &dyn Palette, order: PaletteOrder) -> ColorMap {
+        ColorMap::new(sorted(palette, order))
+    }
+
+    /// A map that splits the domain into one equal step per color of `palette`, sorted by
+    /// `order`, without blending between them.
+    pub fn stepped(palette: &dyn Palette, order: PaletteOrder) -> ColorMap {
+        ColorMap {
+            mapping: Mapping::Stepped(sorted(palette, order)),
+            domain: (0.0, 1.0),
+        }
+    }
+
+    /// Dark blue through green to yellow, perceptually uniform.
+    pub fn viridis() -> ColorMap {
+        ColorMap::from_hex(&[
+            "#440154", "#472d7b", "#3b528b", "#2c728e", "#21918c", "#28ae80", "#5ec962", "#addc30",
+            "#fde725",
+        ])
+    }
+
+    /// Black through purple and red to pale yellow, perceptually uniform.
+    pub fn magma() -> ColorMap {
+        ColorMap::from_hex(&[
+            "#000004", "#1c1044", "#4f127b", "#812581", "#b5367a", "#e55064", "#fb8761", "#fec287",
+            "#fcfdbf",
+        ])
+    }
+
+    /// Dark blue through grey to yellow, perceptually uniform and readable with red-green
+    /// color vision deficiency.
+    pub fn cividis() -> ColorMap {
+        ColorMap::from_hex(&[
+            "#00224e", "#123570", "#3b496c", "#575d6d", "#707173", "#8a8678", "#a59c74", "#c3b369",
+            "#fee838",
+        ])
+    }
+
+    /// The range of values that is mapped onto the colors, `min` gets the first color and
+    /// `max` gets the last color. `min` can be larger than `max` to reverse the map.
+    pub fn domain(mut self, min: f64, max: f64) -> ColorMap {
+        self.domain = (min, max);
+        self
+    }
+
+    /// The color for `value`, NaN gets the first color. Returns `None` if the map has no
+    /// colors.
+    pub fn color_at(&self, value: f64) -> Option<Color> {
+        let (min, max) = self.domain;
+        let t = if min == max {
+            0.0
+        } else {
+            ((value - min) / (max - min)).clamp(0.0, 1.0)
+        };
+
+        match &self.mapping {
+            Mapping::Continuous(gradient) => gradient.color_at(t),
+            Mapping::Stepped(colors) => {
+                let step = (t * colors.len() as f64) as usize;
+                colors
+                    .get(step.min(colors.len().saturating_sub(1)))
+                    .copied()
+            }
+        }
+    }
+
+    fn from_hex(colors: &[&'static str]) -> ColorMap {
+        ColorMap::new(colors.iter().map(|hex| Color::Hex(hex)).collect())
+    }
+}
+
+impl Palette for ColorMap {
+    /// The color for a random value in the domain.
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color> {
+        let (min, max) = self.domain;
+        self.color_at(min + rng.gen::<f64>() * (max - min))
+    }
+
+    fn weighted_colors(&self) -> Vec<(Color, usize)> {
+        match &self.mapping {
+            Mapping::Continuous(gradient) => gradient.weighted_colors(),
+            Mapping::Stepped(colors) => colors.iter().map(|color| (*color, 1)).collect(),
+        }
+    }
+}
+
+fn sorted(palette: &dyn Palette, order: PaletteOrder) -> Vec<Color> {
+    let mut colors = palette.colors();
+
+    match order {
+        PaletteOrder::Lightness => colors.sort_by(|a, b| a.oklab().0.total_cmp(&b.oklab().0)),
+        PaletteOrder::Hue => colors.sort_by(|a, b| hue(a).total_cmp(&hue(b))),
+    }
+
+    colors
+}
+
+fn hue(color: &Color) -> f64 {
+    let (_, a, b, _) = color.oklab();
+    b.atan2(a).to_degrees().rem_euclid(360.0)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::palette::{color::Color, simple_palette::SimplePalette};
+
+    use super::{ColorMap, PaletteOrder};
+
+    fn hex(color: Option<Color>) -> String {
+        color.map(|color| color.to_hex()).unwrap_or_default()
+    }
+
+    #[test]
+    fn built_in_maps() {
+        for map in [ColorMap::viridis(), ColorMap::magma(), ColorMap::cividis()] {
+            let lightness = (0..=10)
+                .map(|i| map.color_at(i as f64 / 10.0).unwrap().oklab().0)
+                .collect::<Vec<f64>>();
+
+            assert!(lightness.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+
+        assert_eq!(hex(ColorMap::viridis().color_at(0.0)), "#440154");
+        assert_eq!(hex(ColorMap::viridis().color_at(2.0)), "#fde725");
+    }
+
+    #[test]
+    fn domain() {
+        let map = ColorMap::new(vec![Color::Hex("#000"), Color::Hex("#fff")]).domain(10.0, 20.0);
+
+        assert_eq!(hex(map.color_at(5.0)), "#000000");
+        assert_eq!(hex(map.color_at(20.0)), "#ffffff");
+
+        let reversed = map.domain(20.0, 10.0);
+        assert_eq!(hex(reversed.color_at(20.0)), "#000000");
+    }
+
+    #[test]
+    fn nan() {
+        let palette = SimplePalette::new([Color::Hex("#000"), Color::Hex("#fff")]);
+
+        for map in [
+            ColorMap::continuous(&palette, PaletteOrder::Lightness),
+            ColorMap::stepped(&palette, PaletteOrder::Lightness),
+        ] {
+            assert_eq!(hex(map.color_at(f64::NAN)), "#000000");
+        }
+    }
+
+    #[test]
+    fn stepped_by_lightness() {
+        let palette = SimplePalette::new([
+            Color::Hex("#ffffff"),
+            Color::Hex("#000000"),
+            Color::Hex("#808080"),
+        ]);
+        let map = ColorMap::stepped(&palette, PaletteOrder::Lightness);
+
+        assert_eq!(hex(map.color_at(0.1)), "#000000");
+        assert_eq!(hex(map.color_at(0.5)), "#808080");
+        assert_eq!(hex(map.color_at(0.7)), "#ffffff");
+        assert_eq!(hex(map.color_at(1.0)), "#ffffff");
+    }
+
+    #[test]
+    fn continuous_by_hue() {
+        let palette = SimplePalette::new([
+            Color::Hex("#0000ff"),
+            Color::Hex("#ff0000"),
+            Color::Hex("#00ff00"),
+        ]);
+        let map = ColorMap::continuous(&palette, PaletteOrder::Hue);
+
+        assert_eq!(hex(map.color_at(0.0)), "#ff0000");
+        assert_eq!(hex(map.color_at(0.5)), "#00ff00");
+        assert_eq!(hex(map.color_at(1.0)), "#0000ff");
+
+        let empty = ColorMap::continuous(&SimplePalette::new([]), PaletteOrder::Hue);
+        assert_eq!(empty.color_at(0.5), None);
+    }
+}
@@ -0,0 +1,232 @@
+use std::path::Path;
+
+use image::{ImageResult, RgbaImage};
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use super::{color::Color, weighted_palette::WeightedPalette};
+
+/// The most pixels that are clustered, larger images are sampled down to this many pixels.
+const MAX_SAMPLES: usize = 20_000;
+
+/// The most k-means iterations, most images settle well before this.
+const MAX_ITERATIONS: usize = 30;
+
+/**
+Extract the `N` dominant colors of an image file, such as a PNG or JPEG, together with a
+suggested background. The colors are clustered with k-means in OKLab and weighted by how
+many pixels they cover. The background is the most common cluster that is dark or light
+enough to sit behind the other colors, and is not part of the palette. The same `seed`
+always gives the same palette.
+
+Example
+
+```no_run
+use art::palette::{extract::extract_palette, Palette};
+
+let (background, palette) = extract_palette::<5, _>("reference.jpg", 1).unwrap();
+let color = palette.get_random_color(&mut rand::thread_rng());
+```
+*/
+pub fn extract_palette<const N: usize, P: AsRef<Path>>(
+    path: P,
+    seed: u64,
+) -> ImageResult<(Color, WeightedPalette<N>)> {
+    let image = image::open(path)?.to_rgba8();
+    Ok(palette_from_image(&image, seed))
+}
+
+/// Same as [`extract_palette`], for an image that is already loaded.
+pub fn palette_from_image<const N: usize>(
+    image: &RgbaImage,
+    seed: u64,
+) -> (Color, WeightedPalette<N>) {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    let pixels = image
+        .pixels()
+        .filter(|pixel| pixel.0[3] > 0)
+        .collect::<Vec<&image::Rgba<u8>>>();
+
+    let samples = if pixels.len() > MAX_SAMPLES {
+        (0..MAX_SAMPLES)
+            .map(|_| pixels[rng.gen_range(0..pixels.len())])
+            .collect::<Vec<&image::Rgba<u8>>>()
+    } else {
+        pixels
+    };
+
+    let points = samples
+        .iter()
+        .map(|pixel| {
+            let (l, a, b, _) =
+                Color::from(image::Rgba([pixel.0[0], pixel.0[1], pixel.0[2], 255])).oklab();
+            [l, a, b]
+        })
+        .collect::<Vec<[f64; 3]>>();
+
+    let mut clusters = k_means(&points, N + 1, &mut rng);
+    clusters.sort_by_key(|(_, weight)| std::cmp::Reverse(*weight));
+
+    let background_index = clusters
+        .iter()
+        .position(|(center, weight)| *weight > 0 && (center[0] < 0.3 || center[0] > 0.85))
+        .unwrap_or(0);
+    let (background, _) = clusters.remove(background_index);
+
+    let to_color = |center: [f64; 3]| Color::Oklab(center[0], center[1], center[2], 1.0).to_rgba();
+
+    let colors: [(Color, usize); N] = clusters
+        .into_iter()
+        .map(|(center, weight)| (to_color(center), weight))
+        .collect::<Vec<(Color, usize)>>()
+        .try_into()
+        .unwrap_or_else(|_| unreachable!("k-means returns exactly N + 1 clusters"));
+
+    (to_color(background), WeightedPalette::new(colors))
+}
+
+/// Cluster `points` into `k` clusters, returning the center of every cluster and how many
+/// points belong to it. Clusters that no point belongs to have a weight of `0`.
+fn k_means(points: &[[f64; 3]], k: usize, rng: &mut ChaCha8Rng) -> Vec<([f64; 3], usize)> {
+    if points.is_empty() {
+        return vec![([0.0; 3], 0); k];
+    }
+
+    let mut centers = k_means_plus_plus(points, k, rng);
+    let mut assignments = vec![usize::MAX; points.len()];
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+
+        for (point, assignment) in points.iter().zip(assignments.iter_mut()) {
+            let nearest = nearest(&centers, point);
+            if nearest != *assignment {
+                *assignment = nearest;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![([0.0; 3], 0); k];
+        for (point, assignment) in points.iter().zip(assignments.iter()) {
+            let (sum, count) = &mut sums[*assignment];
+            for axis in 0..3 {
+                sum[axis] += point[axis];
+            }
+            *count += 1;
+        }
+
+        for (center, (sum, count)) in centers.iter_mut().zip(sums) {
+            if count > 0 {
+                *center = sum.map(|value| value / count as f64);
+            }
+        }
+    }
+
+    let mut weights = vec![0; k];
+    for assignment in assignments {
+        weights[assignment] += 1;
+    }
+
+    centers.into_iter().zip(weights).collect()
+}
+
+/// Pick `k` starting centers, each one likely to be far away from the ones before it.
+fn k_means_plus_plus(points: &[[f64; 3]], k: usize, rng: &mut ChaCha8Rng) -> Vec<[f64; 3]> {
+    let mut centers = vec![points[rng.gen_range(0..points.len())]];
+
+    while centers.len() < k {
+        let distances = points
+            .iter()
+            .map(|point| distance(&centers[nearest(&centers, point)], point))
+            .collect::<Vec<f64>>();
+
+        match WeightedIndex::new(&distances) {
+            Ok(dist) => centers.push(points[dist.sample(rng)]),
+            // Every point is already a center, duplicate one so there are k clusters.
+            Err(_) => centers.push(centers[0]),
+        }
+    }
+
+    centers
+}
+
+fn nearest(centers: &[[f64; 3]], point: &[f64; 3]) -> usize {
+    centers
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| distance(a, point).total_cmp(&distance(b, point)))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Squared euclidean distance.
+fn distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+#[cfg(test)]
+mod test {
+    use image::{ImageBuffer, RgbaImage};
+
+    use crate::palette::Palette;
+
+    use super::palette_from_image;
+
+    fn image() -> RgbaImage {
+        ImageBuffer::from_fn(10, 10, |x, y| match (x, y) {
+            (0..=2, _) => image::Rgba([250, 20, 20, 255]),
+            (3, 0..=4) => image::Rgba([20, 20, 250, 255]),
+            _ => image::Rgba([10, 10, 12, 255]),
+        })
+    }
+
+    #[test]
+    fn dominant_colors() {
+        let (background, palette) = palette_from_image::<2>(&image(), 1);
+
+        assert_eq!(background.to_hex(), "#0a0a0c");
+        assert_eq!(
+            palette
+                .weighted_colors()
+                .into_iter()
+                .map(|(color, weight)| (color.to_hex(), weight))
+                .collect::<Vec<(String, usize)>>(),
+            vec![(String::from("#fa1414"), 30), (String::from("#1414fa"), 5)]
+        );
+    }
+
+    #[test]
+    fn deterministic() {
+        let mut noisy = image();
+        for (i, pixel) in noisy.pixels_mut().enumerate() {
+            pixel.0[1] = (i * 37 % 256) as u8;
+        }
+
+        let (a, palette_a) = palette_from_image::<3>(&noisy, 7);
+        let (b, palette_b) = palette_from_image::<3>(&noisy, 7);
+
+        assert_eq!(a, b);
+        assert_eq!(palette_a.weighted_colors(), palette_b.weighted_colors());
+    }
+
+    #[test]
+    fn fewer_colors_than_clusters() {
+        let image: RgbaImage = ImageBuffer::from_pixel(4, 4, image::Rgba([255, 255, 255, 255]));
+        let (background, palette) = palette_from_image::<3>(&image, 1);
+
+        assert_eq!(background.to_hex(), "#ffffff");
+        assert_eq!(
+            palette
+                .weighted_colors()
+                .iter()
+                .map(|(_, weight)| weight)
+                .sum::<usize>(),
+            0
+        );
+    }
+}
@@ -0,0 +1,397 @@
+use image::RgbImage;
+
+use crate::svg::document::Document;
+
+use super::color::{linear_to_oklab, linear_to_srgb, oklab_to_linear, srgb_to_linear, Color};
+
+/**
+A tone curve through control points, mapping input brightness to output brightness with
+both in `0..=1`. The curve passes smoothly through every point without overshooting, and
+black and white stay pinned at `(0, 0)` and `(1, 1)` unless other points are given for
+them.
+
+Example
+
+```
+use art::palette::grade::Curve;
+
+let lift_shadows = Curve::new(vec![(0.25, 0.35)]);
+let punchy = Curve::contrast(0.5);
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    points: Vec<(f64, f64)>,
+    tangents: Vec<f64>,
+}
+
+impl Curve {
+    /// A curve through `points`, given as `(input, output)` pairs.
+    pub fn new(points: Vec<(f64, f64)>) -> Curve {
+        let mut points = points
+            .into_iter()
+            .map(|(x, y)| (x.clamp(0.0, 1.0), y.clamp(0.0, 1.0)))
+            .collect::<Vec<(f64, f64)>>();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        points.dedup_by(|a, b| a.0 == b.0);
+
+        if points.first().map(|(x, _)| *x) != Some(0.0) {
+            points.insert(0, (0.0, 0.0));
+        }
+        if points.last().map(|(x, _)| *x) != Some(1.0) {
+            points.push((1.0, 1.0));
+        }
+
+        let tangents = tangents(&points);
+        Curve { points, tangents }
+    }
+
+    /// An S-shaped curve that darkens the shadows and brightens the highlights for a positive
+    /// `amount`, or flattens the image for a negative one. `1.0` is a strong effect.
+    pub fn contrast(amount: f64) -> Curve {
+        let offset = 0.1 * amount.clamp(-1.0, 1.0);
+        Curve::new(vec![(0.25, 0.25 - offset), (0.75, 0.75 + offset)])
+    }
+
+    /// The output for `input`.
+    pub fn value_at(&self, input: f64) -> f64 {
+        let x = input.clamp(0.0, 1.0);
+        let i = self
+            .points
+            .windows(2)
+            .position(|pair| x <= pair[1].0)
+            .unwrap_or(self.points.len() - 2);
+
+        let ((x0, y0), (x1, y1)) = (self.points[i], self.points[i + 1]);
+        let h = x1 - x0;
+        let t = (x - x0) / h;
+        let (t2, t3) = (t * t, t * t * t);
+
+        let value = (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+            + (t3 - 2.0 * t2 + t) * h * self.tangents[i]
+            + (-2.0 * t3 + 3.0 * t2) * y1
+            + (t3 - t2) * h * self.tangents[i + 1];
+
+        value.clamp(0.0, 1.0)
+    }
+}
+
+/// Monotone tangents for a cubic Hermite spline, using the Fritsch-Carlson method.
+fn tangents(points: &[(f64, f64)]) -> Vec<f64> {
+    let slopes = points
+        .windows(2)
+        .map(|pair| (pair[1].1 - pair[0].1) / (pair[1].0 - pair[0].0))
+        .collect::<Vec<f64>>();
+
+    let mut tangents = (0..points.len())
+        .map(
+            |i| match (i.checked_sub(1).map(|i| slopes[i]), slopes.get(i).copied()) {
+                (Some(before), Some(after)) if before * after > 0.0 => (before + after) / 2.0,
+                (Some(_), Some(_)) => 0.0,
+                (Some(slope), None) | (None, Some(slope)) => slope,
+                (None, None) => 0.0,
+            },
+        )
+        .collect::<Vec<f64>>();
+
+    for (i, slope) in slopes.iter().enumerate() {
+        if *slope == 0.0 {
+            tangents[i] = 0.0;
+            tangents[i + 1] = 0.0;
+            continue;
+        }
+
+        let (alpha, beta) = (tangents[i] / slope, tangents[i + 1] / slope);
+        let length = alpha.hypot(beta);
+        if length > 3.0 {
+            tangents[i] = 3.0 / length * alpha * slope;
+            tangents[i + 1] = 3.0 / length * beta * slope;
+        }
+    }
+
+    tangents
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Adjustment {
+    WhiteBalance { temperature: f64, tint: f64 },
+    Saturation(f64),
+    Levels { black: f64, white: f64, gamma: f64 },
+    Curve(Curve),
+    Grayscale,
+}
+
+impl Adjustment {
+    /// Adjust gamma encoded sRGB channels in `0..=1`.
+    fn apply(&self, rgb: [f64; 3]) -> [f64; 3] {
+        match self {
+            Adjustment::WhiteBalance { temperature, tint } => {
+                let gains = [
+                    1.0 + 0.25 * temperature,
+                    1.0 - 0.25 * tint,
+                    1.0 - 0.25 * temperature,
+                ];
+                let [r, g, b] = rgb.map(srgb_to_linear);
+
+                [r * gains[0], g * gains[1], b * gains[2]]
+                    .map(|channel| linear_to_srgb(channel.clamp(0.0, 1.0)))
+            }
+            Adjustment::Saturation(factor) => {
+                let [r, g, b] = rgb.map(srgb_to_linear);
+                let (l, a, b) = linear_to_oklab(r, g, b);
+                let (r, g, b) = oklab_to_linear(l, a * factor, b * factor);
+
+                [r, g, b].map(|channel| linear_to_srgb(channel.clamp(0.0, 1.0)))
+            }
+            Adjustment::Levels {
+                black,
+                white,
+                gamma,
+            } => rgb.map(|channel| {
+                let range = (white - black).max(1e-6);
+                ((channel - black) / range)
+                    .clamp(0.0, 1.0)
+                    .powf(1.0 / gamma.max(1e-6))
+            }),
+            Adjustment::Curve(curve) => rgb.map(|channel| curve.value_at(channel)),
+            Adjustment::Grayscale => {
+                let [r, g, b] = rgb.map(srgb_to_linear);
+                [linear_to_srgb(0.2126 * r + 0.7152 * g + 0.0722 * b); 3]
+            }
+        }
+    }
+}
+
+/**
+A sequence of tonal adjustments, applied in the order they were added, that can be run
+over single colors, over every color in a finished [`Document`], or over the pixels of a
+rendered image. This makes it cheap to try several color grades of the same piece
+without generating it again.
+
+Alpha is never changed.
+
+Example
+
+```
+use art::{
+    palette::{
+        color::Color,
+        grade::{ColorGrade, Curve},
+    },
+    shapes::{circle::Circle, point::Point, rectangle::Rectangle},
+    svg::document::Document,
+};
+
+let mut svg = Document::new("graded", Rectangle::new(Point(0.0, 0.0), 100.0, 100.0));
+let mut circle = Circle::new(Point(50.0, 50.0), 20.0);
+circle.set_color(Color::Hex("#5a8fd0"));
+svg.add_shape(Box::new(circle));
+
+let golden_hour = ColorGrade::new()
+    .white_balance(0.4, 0.05)
+    .curve(Curve::contrast(0.3));
+let faded = ColorGrade::new().saturation(0.6).levels(-0.1, 1.0, 1.1);
+
+let mut warm = svg.clone();
+golden_hour.apply_to_document(&mut warm);
+let mut washed_out = svg.clone();
+faded.apply_to_document(&mut washed_out);
+```
+*/
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColorGrade {
+    adjustments: Vec<Adjustment>,
+}
+
+impl ColorGrade {
+    /// A grade that leaves all colors as they are.
+    pub fn new() -> ColorGrade {
+        ColorGrade::default()
+    }
+
+    /// Shift the white balance. A positive `temperature` makes the colors warmer and a
+    /// negative one cooler, a positive `tint` shifts them towards magenta and a negative
+    /// one towards green. Both go from `-1.0` to `1.0`.
+    pub fn white_balance(mut self, temperature: f64, tint: f64) -> ColorGrade {
+        self.adjustments.push(Adjustment::WhiteBalance {
+            temperature: temperature.clamp(-1.0, 1.0),
+            tint: tint.clamp(-1.0, 1.0),
+        });
+        self
+    }
+
+    /// Scale the chroma of every color by `factor`, where `0.0` removes all color, `1.0`
+    /// changes nothing and `2.0` doubles the colorfulness. Colors are clipped to sRGB.
+    pub fn saturation(mut self, factor: f64) -> ColorGrade {
+        self.adjustments
+            .push(Adjustment::Saturation(factor.max(0.0)));
+        self
+    }
+
+    /// Stretch the channels so that `black` becomes black and `white` becomes white, then
+    /// bend the midtones with `gamma`, where values above `1.0` brighten them.
+    pub fn levels(mut self, black: f64, white: f64, gamma: f64) -> ColorGrade {
+        self.adjustments.push(Adjustment::Levels {
+            black,
+            white,
+            gamma,
+        });
+        self
+    }
+
+    /// Run every channel through a tone curve.
+    pub fn curve(mut self, curve: Curve) -> ColorGrade {
+        self.adjustments.push(Adjustment::Curve(curve));
+        self
+    }
+
+    /// Turn every color into the grey of the same luminance.
+    pub fn grayscale(mut self) -> ColorGrade {
+        self.adjustments.push(Adjustment::Grayscale);
+        self
+    }
+
+    /// `color` with all adjustments applied, in the same representation as `color`.
+    pub fn apply(&self, color: &Color) -> Color {
+        let (r, g, b, alpha) = color.srgba();
+        let [r, g, b] = self.apply_rgb([r, g, b]);
+
+        color.same_representation(Color::Rgba(r * 255.0, g * 255.0, b * 255.0, alpha))
+    }
+
+    /// Apply the grade to every color in `document`, see [`Document::map_colors`].
+    pub fn apply_to_document(&self, document: &mut Document<'_>) {
+        document.map_colors(|color| self.apply(&color));
+    }
+
+    /// Apply the grade to every pixel of `image`.
+    pub fn apply_to_image(&self, image: &mut RgbImage) {
+        for pixel in image.pixels_mut() {
+            let rgb = self.apply_rgb(pixel.0.map(|channel| channel as f64 / 255.0));
+            pixel.0 = rgb.map(|channel| (channel * 255.0).round() as u8);
+        }
+    }
+
+    fn apply_rgb(&self, rgb: [f64; 3]) -> [f64; 3] {
+        self.adjustments.iter().fold(
+            rgb.map(|channel| channel.clamp(0.0, 1.0)),
+            |rgb, adjustment| adjustment.apply(rgb),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use image::{Rgb, RgbImage};
+
+    use crate::palette::color::Color;
+
+    use super::{ColorGrade, Curve};
+
+    #[test]
+    fn identity() {
+        let grade = ColorGrade::new();
+
+        for color in [
+            Color::Hex("#5a8fd0"),
+            Color::HSLa(20, 80.0, 30.0, 0.5),
+            Color::Oklch(0.7, 0.1, 140.0, 1.0),
+        ] {
+            let graded = grade.apply(&color);
+            assert_eq!(graded.to_hex(), color.to_hex());
+            assert_eq!(graded.alpha(), color.alpha());
+        }
+
+        assert!(matches!(
+            grade.apply(&Color::HSLa(20, 80.0, 30.0, 0.5)),
+            Color::HSLa(..)
+        ));
+    }
+
+    #[test]
+    fn white_balance() {
+        let grey = Color::Hex("#808080");
+
+        let (r, g, b, _) = ColorGrade::new()
+            .white_balance(0.5, 0.0)
+            .apply(&grey)
+            .srgba();
+        assert!(r > g && g > b, "{r} {g} {b}");
+
+        let (r, g, b, _) = ColorGrade::new()
+            .white_balance(-0.5, 0.0)
+            .apply(&grey)
+            .srgba();
+        assert!(r < g && g < b, "{r} {g} {b}");
+
+        let (r, g, b, _) = ColorGrade::new()
+            .white_balance(0.0, 0.5)
+            .apply(&grey)
+            .srgba();
+        assert!(g < r && r == b, "{r} {g} {b}");
+    }
+
+    #[test]
+    fn saturation_and_grayscale() {
+        let color = Color::Hex("#5a8fd0");
+
+        let (_, a, b, _) = ColorGrade::new().saturation(0.0).apply(&color).oklab();
+        assert!(a.abs() < 1e-3 && b.abs() < 1e-3, "{a} {b}");
+
+        let (_, a1, b1, _) = color.oklab();
+        let (_, a2, b2, _) = ColorGrade::new().saturation(1.2).apply(&color).oklab();
+        assert!(a2.hypot(b2) > a1.hypot(b1));
+
+        let grey = ColorGrade::new().grayscale().apply(&color);
+        let (r, g, b, _) = grey.srgba();
+        assert!((r - g).abs() < 1e-9 && (g - b).abs() < 1e-9);
+        assert!((grey.relative_luminance() - color.relative_luminance()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn levels() {
+        let grade = ColorGrade::new().levels(0.2, 0.8, 1.0);
+
+        assert_eq!(grade.apply(&Color::Hex("#333333")).to_hex(), "#000000");
+        assert_eq!(grade.apply(&Color::Hex("#cccccc")).to_hex(), "#ffffff");
+        assert_eq!(grade.apply(&Color::Hex("#808080")).to_hex(), "#808080");
+
+        let (l, ..) = ColorGrade::new()
+            .levels(0.0, 1.0, 2.0)
+            .apply(&Color::Hex("#808080"))
+            .srgba();
+        assert!(l > 0.7);
+    }
+
+    #[test]
+    fn curves() {
+        let curve = Curve::new(vec![(0.25, 0.35), (0.75, 0.7)]);
+
+        assert_eq!(curve.value_at(0.0), 0.0);
+        assert!((curve.value_at(0.25) - 0.35).abs() < 1e-9);
+        assert!((curve.value_at(0.75) - 0.7).abs() < 1e-9);
+        assert_eq!(curve.value_at(1.0), 1.0);
+
+        let values = (0..=100)
+            .map(|i| curve.value_at(i as f64 / 100.0))
+            .collect::<Vec<f64>>();
+        assert!(values.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        let contrast = Curve::contrast(1.0);
+        assert!(contrast.value_at(0.2) < 0.2 && contrast.value_at(0.8) > 0.8);
+        assert!((contrast.value_at(0.5) - 0.5).abs() < 1e-9);
+        assert_eq!(Curve::new(vec![]).value_at(0.3), 0.3);
+    }
+
+    #[test]
+    fn image() {
+        let mut image = RgbImage::from_pixel(2, 2, Rgb([255, 0, 0]));
+        image.put_pixel(1, 1, Rgb([128, 128, 128]));
+
+        ColorGrade::new().grayscale().apply_to_image(&mut image);
+
+        let [r, g, b] = image.get_pixel(0, 0).0;
+        assert!(r == g && g == b && r > 100 && r < 150, "{r}");
+        assert_eq!(image.get_pixel(1, 1).0, [128, 128, 128]);
+    }
+}
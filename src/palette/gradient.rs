@@ -0,0 +1,210 @@
+use rand::{Rng, RngCore};
+
+use super::{
+    color::{Color, ColorSpace},
+    Palette,
+};
+
+/**
+A color ramp made up of colors positioned between `0` and `1`. Sampling the gradient at
+any `t` blends the two closest stops, by default in [`ColorSpace::Oklab`] so that the
+transitions look even.
+
+Example
+
+```
+use art::palette::{color::Color, gradient::Gradient};
+
+let gradient = Gradient::new(vec![
+    (0.0, Color::Hex("#181D31")),
+    (0.7, Color::Hex("#E1B31E")),
+    (1.0, Color::Hex("#F0E9D2")),
+]);
+
+// Color a stroke by some value in 0..1, like a noise value or the length of a path.
+let color = gradient.color_at(0.35);
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f64, Color)>,
+    space: ColorSpace,
+}
+
+impl Gradient {
+    /// Create a new gradient from `(position, color)` stops. Positions are clamped
+    /// to `0..=1`, NaN counts as `0`, and the stops do not have to be sorted.
+    pub fn new(stops: Vec<(f64, Color)>) -> Gradient {
+        let mut gradient = Gradient {
+            stops: vec![],
+            space: ColorSpace::default(),
+        };
+
+        for (position, color) in stops {
+            gradient.add_stop(position, color);
+        }
+
+        gradient
+    }
+
+    /// Create a new gradient where the colors are spread out evenly, the first
+    /// color at `0` and the last color at `1`.
+    pub fn evenly_spaced(colors: Vec<Color>) -> Gradient {
+        let steps = colors.len().saturating_sub(1).max(1) as f64;
+
+        Gradient::new(
+            colors
+                .into_iter()
+                .enumerate()
+                .map(|(i, color)| (i as f64 / steps, color))
+                .collect(),
+        )
+    }
+
+    /// Blend the stops in the given color space instead of OKLab.
+    pub fn interpolate_in(mut self, space: ColorSpace) -> Gradient {
+        self.space = space;
+        self
+    }
+
+    /// Add another stop to the gradient. A stop that is added at the same position as
+    /// an existing stop will be placed after it, which gives a hard edge.
+    pub fn add_stop(&mut self, position: f64, color: Color) {
+        let position = clamp_unit(position);
+        let index = self.stops.partition_point(|(p, _)| *p <= position);
+
+        self.stops.insert(index, (position, color));
+    }
+
+    /// The `(position, color)` stops of this gradient, sorted by position.
+    pub fn stops(&self) -> &[(f64, Color)] {
+        &self.stops
+    }
+
+    /// The color at `t`, where `t` is clamped to `0..=1` and NaN counts as `0`. Returns
+    /// `None` if the gradient has no stops.
+    pub fn color_at(&self, t: f64) -> Option<Color> {
+        let t = clamp_unit(t);
+        let first = self.stops.first()?;
+        let last = self.stops.last()?;
+
+        if t <= first.0 {
+            return Some(first.1);
+        }
+
+        if t >= last.0 {
+            return Some(last.1);
+        }
+
+        let index = self.stops.partition_point(|(p, _)| *p <= t);
+        let (from_position, from) = self.stops[index - 1];
+        let (to_position, to) = self.stops[index];
+        let local_t = (t - from_position) / (to_position - from_position);
+
+        Some(from.interpolate(&to, local_t, self.space))
+    }
+
+    /// `count` colors sampled at even intervals along the gradient.
+    pub fn sample(&self, count: usize) -> Vec<Color> {
+        let steps = count.saturating_sub(1).max(1) as f64;
+
+        (0..count)
+            .filter_map(|i| self.color_at(i as f64 / steps))
+            .collect()
+    }
+}
+
+/// `value` clamped to `0..=1`, with NaN, for example from noise, as `0`.
+fn clamp_unit(value: f64) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, 1.0)
+    }
+}
+
+impl Palette for Gradient {
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color> {
+        self.color_at(rng.gen())
+    }
+
+    fn weighted_colors(&self) -> Vec<(Color, usize)> {
+        self.stops.iter().map(|(_, color)| (*color, 1)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::palette::color::{Color, ColorSpace};
+
+    use super::Gradient;
+
+    #[test]
+    fn empty() {
+        assert_eq!(Gradient::new(vec![]).color_at(0.5), None);
+    }
+
+    #[test]
+    fn clamps_to_outer_stops() {
+        let gradient = Gradient::new(vec![
+            (0.2, Color::Hex("#ff0000")),
+            (0.8, Color::Hex("#0000ff")),
+        ]);
+
+        assert_eq!(gradient.color_at(0.0), Some(Color::Hex("#ff0000")));
+        assert_eq!(gradient.color_at(1.5), Some(Color::Hex("#0000ff")));
+    }
+
+    #[test]
+    fn nan() {
+        let gradient = Gradient::new(vec![
+            (0.0, Color::Hex("#ff0000")),
+            (f64::NAN, Color::Hex("#00ff00")),
+            (1.0, Color::Hex("#0000ff")),
+        ]);
+
+        assert_eq!(gradient.stops()[1], (0.0, Color::Hex("#00ff00")));
+        assert_eq!(gradient.color_at(f64::NAN), Some(Color::Hex("#ff0000")));
+    }
+
+    #[test]
+    fn unsorted_stops() {
+        let gradient = Gradient::new(vec![
+            (1.0, Color::Hex("#ffffff")),
+            (0.0, Color::Hex("#000000")),
+        ])
+        .interpolate_in(ColorSpace::Srgb);
+
+        assert_eq!(gradient.color_at(0.0), Some(Color::Hex("#000000")));
+        assert_eq!(gradient.color_at(0.25).unwrap().to_hex(), "#404040");
+    }
+
+    #[test]
+    fn hard_edge() {
+        let gradient = Gradient::new(vec![
+            (0.0, Color::Hex("#ff0000")),
+            (0.5, Color::Hex("#ff0000")),
+            (0.5, Color::Hex("#0000ff")),
+            (1.0, Color::Hex("#0000ff")),
+        ]);
+
+        assert_eq!(gradient.color_at(0.49).unwrap().to_hex(), "#ff0000");
+        assert_eq!(gradient.color_at(0.51).unwrap().to_hex(), "#0000ff");
+    }
+
+    #[test]
+    fn evenly_spaced() {
+        let gradient = Gradient::evenly_spaced(vec![
+            Color::Hex("#000000"),
+            Color::Hex("#ff0000"),
+            Color::Hex("#ffffff"),
+        ]);
+
+        assert_eq!(
+            gradient.stops().iter().map(|s| s.0).collect::<Vec<f64>>(),
+            vec![0.0, 0.5, 1.0]
+        );
+        assert_eq!(gradient.color_at(0.5).unwrap().to_hex(), "#ff0000");
+        assert_eq!(gradient.sample(3).len(), 3);
+    }
+}
@@ -0,0 +1,281 @@
+use rand::{distributions::WeightedIndex, prelude::Distribution, RngCore};
+
+use super::{color::Color, Palette};
+
+/// Rules for picking hues that go well together, relative to a base hue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HarmonyRule {
+    /// The base hue and the hue opposite to it.
+    Complementary,
+
+    /// Three hues evenly spread around the color wheel.
+    Triadic,
+
+    /// Four hues evenly spread around the color wheel.
+    Tetradic,
+
+    /// The base hue and its two neighbors, 30 degrees to each side.
+    Analogous,
+
+    /// The base hue and the two hues next to its complement.
+    SplitComplementary,
+
+    /// Only the base hue, in different shades.
+    Monochrome,
+}
+
+impl HarmonyRule {
+    /// Hue offsets in degrees from the base hue.
+    pub fn hue_offsets(&self) -> Vec<f64> {
+        match self {
+            HarmonyRule::Complementary => vec![0.0, 180.0],
+            HarmonyRule::Triadic => vec![0.0, 120.0, 240.0],
+            HarmonyRule::Tetradic => vec![0.0, 90.0, 180.0, 270.0],
+            HarmonyRule::Analogous => vec![-30.0, 0.0, 30.0],
+            HarmonyRule::SplitComplementary => vec![0.0, 150.0, 210.0],
+            HarmonyRule::Monochrome => vec![0.0],
+        }
+    }
+}
+
+/**
+A palette generated from a base color and a [`HarmonyRule`]. The hues are picked in
+OKLCH, so all colors keep the perceived lightness and chroma of the base color, and
+each hue can be spread out into lighter and darker shades.
+
+Example
+
+```
+use art::palette::{
+    color::Color,
+    harmony::{Harmony, HarmonyRule},
+    Palette,
+};
+
+let (background, palette) = Harmony::new(Color::Hex("#E1B31E"), HarmonyRule::Triadic)
+    .shades(3)
+    .lightness_spread(0.15)
+    .generate();
+
+let color = palette.get_random_color(&mut rand::thread_rng());
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Harmony {
+    base: Color,
+    rule: HarmonyRule,
+    shades: usize,
+    lightness_spread: f64,
+    chroma_spread: f64,
+}
+
+impl Harmony {
+    /// Create a new harmony around `base`. Monochrome harmonies get 5 shades, the
+    /// other rules get one color per hue.
+    pub fn new(base: Color, rule: HarmonyRule) -> Harmony {
+        Harmony {
+            base,
+            rule,
+            shades: match rule {
+                HarmonyRule::Monochrome => 5,
+                _ => 1,
+            },
+            lightness_spread: 0.2,
+            chroma_spread: 0.5,
+        }
+    }
+
+    /// The number of shades for every hue, from darkest to lightest.
+    pub fn shades(&mut self, shades: usize) -> Harmony {
+        self.shades = shades.max(1);
+        *self
+    }
+
+    /// How far the darkest and lightest shades are from the base lightness, in OKLCH
+    /// lightness where `1.0` is the whole range from black to white.
+    pub fn lightness_spread(&mut self, spread: f64) -> Harmony {
+        self.lightness_spread = spread.abs();
+        *self
+    }
+
+    /// How much chroma the darkest and lightest shades lose compared to the base color,
+    /// from `0.0` where all shades are as vivid as the base color, to `1.0` where the
+    /// outermost shades are grey.
+    pub fn chroma_spread(&mut self, spread: f64) -> Harmony {
+        self.chroma_spread = spread.clamp(0.0, 1.0);
+        *self
+    }
+
+    /// A background that fits the harmony: a deep tint of the base hue for light
+    /// palettes, and a pale tint of the base hue for dark palettes.
+    pub fn background(&self) -> Color {
+        let Color::Oklch(_, c, h, _) = self.base.to_oklch() else {
+            unreachable!()
+        };
+
+        let colors = self.colors();
+        let lightness =
+            colors.iter().map(|color| color.oklab().0).sum::<f64>() / colors.len() as f64;
+
+        if lightness > 0.55 {
+            fit_gamut(0.2, c * 0.2, h)
+        } else {
+            fit_gamut(0.96, c * 0.1, h)
+        }
+    }
+
+    /// The background and the palette, in the same shape as the predefined
+    /// [`super::palettes::Palettes`].
+    pub fn generate(&self) -> (Color, Box<dyn Palette>) {
+        (self.background(), Box::new(*self))
+    }
+}
+
+impl Palette for Harmony {
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color> {
+        let colors = self.weighted_colors();
+        let dist = WeightedIndex::new(colors.iter().map(|(_, weight)| *weight)).ok()?;
+
+        Some(colors[dist.sample(rng)].0)
+    }
+
+    /// The colors of the harmony, grouped by hue and ordered from dark to light. The shades
+    /// of the base hue are weighted twice as heavy as the other hues, so the base color
+    /// stays dominant.
+    fn weighted_colors(&self) -> Vec<(Color, usize)> {
+        let Color::Oklch(l, c, h, _) = self.base.to_oklch() else {
+            unreachable!()
+        };
+
+        self.rule
+            .hue_offsets()
+            .into_iter()
+            .flat_map(|offset| {
+                let weight = if offset == 0.0 { 2 } else { 1 };
+
+                (0..self.shades).map(move |shade| {
+                    let t = if self.shades == 1 {
+                        0.0
+                    } else {
+                        shade as f64 / (self.shades - 1) as f64 * 2.0 - 1.0
+                    };
+
+                    let lightness = (l + t * self.lightness_spread).clamp(0.0, 1.0);
+                    let chroma = c * (1.0 - self.chroma_spread * t.abs());
+
+                    (fit_gamut(lightness, chroma, h + offset), weight)
+                })
+            })
+            .collect()
+    }
+}
+
+/// The OKLCH color with the given lightness and hue, with as much of `chroma` as fits
+/// inside the sRGB gamut.
+pub(crate) fn fit_gamut(lightness: f64, chroma: f64, hue: f64) -> Color {
+    let hue = hue.rem_euclid(360.0);
+    let in_gamut = |chroma: f64| {
+        let (r, g, b, _) = Color::Oklch(lightness, chroma, hue, 1.0).srgba();
+        [r, g, b]
+            .iter()
+            .all(|channel| (-1e-6..=1.0 + 1e-6).contains(channel))
+    };
+
+    if in_gamut(chroma) {
+        return Color::Oklch(lightness, chroma, hue, 1.0);
+    }
+
+    let (mut low, mut high) = (0.0, chroma);
+    for _ in 0..24 {
+        let mid = (low + high) / 2.0;
+        if in_gamut(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Color::Oklch(lightness, low, hue, 1.0)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::palette::{color::Color, Palette};
+
+    use super::{Harmony, HarmonyRule};
+
+    fn hue(color: &Color) -> f64 {
+        match color.to_oklch() {
+            Color::Oklch(_, _, h, _) => h,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn hues_follow_rule() {
+        let base = Color::Oklch(0.6, 0.1, 40.0, 1.0);
+
+        let hues = Harmony::new(base, HarmonyRule::Triadic)
+            .colors()
+            .iter()
+            .map(|color| hue(color).round())
+            .collect::<Vec<f64>>();
+        assert_eq!(hues, vec![40.0, 160.0, 280.0]);
+
+        let hues = Harmony::new(base, HarmonyRule::SplitComplementary)
+            .colors()
+            .iter()
+            .map(|color| hue(color).round())
+            .collect::<Vec<f64>>();
+        assert_eq!(hues, vec![40.0, 190.0, 250.0]);
+
+        assert_eq!(
+            Harmony::new(base, HarmonyRule::Complementary)
+                .colors()
+                .len(),
+            2
+        );
+        assert_eq!(Harmony::new(base, HarmonyRule::Tetradic).colors().len(), 4);
+    }
+
+    #[test]
+    fn monochrome_shades() {
+        let base = Color::Oklch(0.5, 0.1, 200.0, 1.0);
+        let colors = Harmony::new(base, HarmonyRule::Monochrome)
+            .lightness_spread(0.2)
+            .chroma_spread(0.0)
+            .colors();
+
+        let lightness = colors
+            .iter()
+            .map(|color| (color.oklab().0 * 100.0).round())
+            .collect::<Vec<f64>>();
+
+        assert_eq!(lightness, vec![30.0, 40.0, 50.0, 60.0, 70.0]);
+        assert!(colors.iter().all(|color| (hue(color) - 200.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn colors_are_in_gamut() {
+        let harmony = Harmony::new(Color::Hex("#00ff00"), HarmonyRule::Tetradic)
+            .shades(4)
+            .lightness_spread(0.4);
+
+        for color in harmony.colors() {
+            let (r, g, b, _) = color.srgba();
+            assert!([r, g, b].iter().all(|c| (-1e-6..=1.0 + 1e-6).contains(c)));
+        }
+    }
+
+    #[test]
+    fn background_contrasts_palette() {
+        let (light_background, _) =
+            Harmony::new(Color::Hex("#181D31"), HarmonyRule::Analogous).generate();
+        let (dark_background, palette) =
+            Harmony::new(Color::Hex("#F0E9D2"), HarmonyRule::Analogous).generate();
+
+        assert!(light_background.relative_luminance() > 0.8);
+        assert!(dark_background.relative_luminance() < 0.1);
+        assert!(palette.get_random_color(&mut rand::thread_rng()).is_some());
+    }
+}
@@ -0,0 +1,249 @@
+use std::f64::consts::TAU;
+
+use rand::{Rng, RngCore};
+
+use super::{color::Color, harmony::fit_gamut, Palette};
+
+/// A distribution of random offsets, centered around zero.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Jitter {
+    /// No offset.
+    #[default]
+    None,
+
+    /// Any offset between `-amount` and `amount`, all equally likely.
+    Uniform(f64),
+
+    /// Normally distributed offsets with the given standard deviation, most offsets are
+    /// small and a few are large.
+    Gaussian(f64),
+}
+
+impl Jitter {
+    /// A random offset from this distribution.
+    pub fn sample(&self, rng: &mut dyn RngCore) -> f64 {
+        match *self {
+            Jitter::None => 0.0,
+            Jitter::Uniform(0.0) => 0.0,
+            Jitter::Uniform(amount) => rng.gen_range(-amount.abs()..=amount.abs()),
+            Jitter::Gaussian(deviation) => {
+                // Box-Muller transform, the first number is kept away from zero for the log.
+                let (u, v) = (1.0 - rng.gen::<f64>(), rng.gen::<f64>());
+                (-2.0 * u.ln()).sqrt() * (TAU * v).cos() * deviation
+            }
+        }
+    }
+}
+
+/// The color space a [`JitteredPalette`] varies the colors in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JitterSpace {
+    /// Hue in degrees, saturation and lightness in percentage points, like [`Color::HSLa`].
+    #[default]
+    Hsl,
+
+    /// Hue in degrees, chroma for saturation, where `0.1` is a big change, and lightness
+    /// from `0.0` to `1.0`, like [`Color::Oklch`]. Changes look the same size across hues.
+    Oklch,
+}
+
+/**
+A palette that varies every color it picks a little, so strokes that share a color
+still look hand made. The hue, saturation and lightness get a random offset from their
+own [`Jitter`], and the results are clamped to valid colors.
+
+The randomness comes from the rng passed to [`Palette::get_random_color`], so a seeded
+rng gives the same colors on every run.
+
+Example
+
+```
+use art::palette::{
+    jitter::{Jitter, JitterSpace, JitteredPalette},
+    palettes::Palettes,
+    Palette,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+let (_, palette) = Palettes::orange_autumn();
+let palette = JitteredPalette::new(palette)
+    .space(JitterSpace::Oklch)
+    .hue(Jitter::Gaussian(4.0))
+    .lightness(Jitter::Uniform(0.03));
+
+let mut rng = ChaCha8Rng::seed_from_u64(1);
+let color = palette.get_random_color(&mut rng);
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct JitteredPalette<P: Palette> {
+    palette: P,
+    space: JitterSpace,
+    hue: Jitter,
+    saturation: Jitter,
+    lightness: Jitter,
+}
+
+impl<P: Palette> JitteredPalette<P> {
+    /// Wrap `palette`, without any jitter until it is configured.
+    pub fn new(palette: P) -> JitteredPalette<P> {
+        JitteredPalette {
+            palette,
+            space: JitterSpace::default(),
+            hue: Jitter::None,
+            saturation: Jitter::None,
+            lightness: Jitter::None,
+        }
+    }
+
+    /// The color space the jitter is applied in, which decides the units of the jitter.
+    pub fn space(mut self, space: JitterSpace) -> JitteredPalette<P> {
+        self.space = space;
+        self
+    }
+
+    /// Jitter of the hue, in degrees.
+    pub fn hue(mut self, jitter: Jitter) -> JitteredPalette<P> {
+        self.hue = jitter;
+        self
+    }
+
+    /// Jitter of the saturation, or the chroma in [`JitterSpace::Oklch`].
+    pub fn saturation(mut self, jitter: Jitter) -> JitteredPalette<P> {
+        self.saturation = jitter;
+        self
+    }
+
+    /// Jitter of the lightness.
+    pub fn lightness(mut self, jitter: Jitter) -> JitteredPalette<P> {
+        self.lightness = jitter;
+        self
+    }
+
+    /// `color` with a random offset applied, in the same representation as `color`.
+    pub fn jitter(&self, color: &Color, rng: &mut dyn RngCore) -> Color {
+        let (dh, ds, dl) = (
+            self.hue.sample(rng),
+            self.saturation.sample(rng),
+            self.lightness.sample(rng),
+        );
+
+        match self.space {
+            JitterSpace::Hsl => color.map_hsla(|h, s, l, a| (h + dh, s + ds, l + dl, a)),
+            JitterSpace::Oklch => {
+                let Color::Oklch(l, c, h, alpha) = color.to_oklch() else {
+                    unreachable!()
+                };
+
+                let jittered = fit_gamut((l + dl).clamp(0.0, 1.0), (c + ds).max(0.0), h + dh);
+                color.same_representation(jittered.with_alpha(alpha))
+            }
+        }
+    }
+}
+
+impl<P: Palette> Palette for JitteredPalette<P> {
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color> {
+        let color = self.palette.get_random_color(rng)?;
+        Some(self.jitter(&color, rng))
+    }
+
+    /// The colors of the wrapped palette, without jitter.
+    fn weighted_colors(&self) -> Vec<(Color, usize)> {
+        self.palette.weighted_colors()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::palette::{color::Color, simple_palette::SimplePalette, Palette};
+
+    use super::{Jitter, JitterSpace, JitteredPalette};
+
+    fn palette() -> SimplePalette<2> {
+        SimplePalette::new([Color::HSLa(200, 50.0, 50.0, 1.0), Color::Hex("#ffffff")])
+    }
+
+    #[test]
+    fn without_jitter() {
+        let palette = JitteredPalette::new(palette());
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+        for _ in 0..10 {
+            let color = palette.get_random_color(&mut rng).unwrap();
+            assert!(palette
+                .colors()
+                .iter()
+                .any(|c| c.to_hex() == color.to_hex()));
+        }
+    }
+
+    #[test]
+    fn uniform_hsl_stays_in_range() {
+        let palette = JitteredPalette::new(palette())
+            .hue(Jitter::Uniform(10.0))
+            .saturation(Jitter::Uniform(5.0))
+            .lightness(Jitter::Uniform(5.0));
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+
+        for _ in 0..100 {
+            let color = palette.jitter(&Color::HSLa(200, 50.0, 50.0, 1.0), &mut rng);
+            let Color::HSLa(h, s, l, a) = color else {
+                panic!("expected HSLa, got {color:?}")
+            };
+
+            assert!((190..=210).contains(&h), "{h}");
+            assert!((45.0..=55.0).contains(&s) && (45.0..=55.0).contains(&l));
+            assert_eq!(a, 1.0);
+
+            let (_, _, l, _) = palette.jitter(&Color::Hex("#fff"), &mut rng).hsla();
+            assert!((95.0..=100.0).contains(&l), "{l}");
+        }
+    }
+
+    #[test]
+    fn gaussian_oklch() {
+        let palette = JitteredPalette::new(palette())
+            .space(JitterSpace::Oklch)
+            .lightness(Jitter::Gaussian(0.02));
+        let base = Color::Oklch(0.5, 0.05, 120.0, 0.5);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+
+        let lightness = (0..2000)
+            .map(|_| match palette.jitter(&base, &mut rng) {
+                Color::Oklch(l, _, _, alpha) => {
+                    assert_eq!(alpha, 0.5);
+                    l
+                }
+                color => panic!("expected Oklch, got {color:?}"),
+            })
+            .collect::<Vec<f64>>();
+
+        let mean = lightness.iter().sum::<f64>() / lightness.len() as f64;
+        let deviation = (lightness.iter().map(|l| (l - mean).powi(2)).sum::<f64>()
+            / lightness.len() as f64)
+            .sqrt();
+
+        assert!((mean - 0.5).abs() < 0.002, "{mean}");
+        assert!((deviation - 0.02).abs() < 0.002, "{deviation}");
+    }
+
+    #[test]
+    fn seeded() {
+        let palette = JitteredPalette::new(Box::new(palette()) as Box<dyn Palette>)
+            .hue(Jitter::Gaussian(10.0));
+        let colors = |seed| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            (0..10)
+                .filter_map(|_| palette.get_random_color(&mut rng))
+                .collect::<Vec<Color>>()
+        };
+
+        assert_eq!(colors(4), colors(4));
+        assert_ne!(colors(4), colors(5));
+    }
+}
@@ -0,0 +1,274 @@
+use super::{
+    color::{linear_to_srgb, srgb_to_linear, Color},
+    Palette,
+};
+
+/// Forms of color vision deficiency that a palette can be checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorVision {
+    /// No working red cones.
+    Protanopia,
+
+    /// No working green cones, the most common form.
+    Deuteranopia,
+
+    /// No working blue cones.
+    Tritanopia,
+}
+
+impl ColorVision {
+    /// All the simulated forms of color vision deficiency.
+    pub const ALL: [ColorVision; 3] = [
+        ColorVision::Protanopia,
+        ColorVision::Deuteranopia,
+        ColorVision::Tritanopia,
+    ];
+
+    /**
+    How `color` looks to someone with this color vision deficiency, simulated with the
+    full severity matrices from Machado, Oliveira and Fernandes (2009).
+
+    Example
+
+    ```
+    use art::palette::{color::Color, legibility::ColorVision};
+
+    let red = ColorVision::Deuteranopia.simulate(&Color::Hex("#f00"));
+    let green = ColorVision::Deuteranopia.simulate(&Color::Hex("#0a0"));
+    assert!(red.delta_e_2000(&green) < 10.0);
+    ```
+    */
+    pub fn simulate(&self, color: &Color) -> Color {
+        let matrix = match self {
+            ColorVision::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            ColorVision::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            ColorVision::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        };
+
+        let (r, g, b, a) = color.srgba();
+        let rgb = [r, g, b].map(|channel| srgb_to_linear(channel.clamp(0.0, 1.0)));
+        let [r, g, b] = matrix.map(|row| {
+            let linear = row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2];
+            linear_to_srgb(linear.clamp(0.0, 1.0)) * 255.0
+        });
+
+        Color::Rgba(r, g, b, a)
+    }
+}
+
+/// Two colors that can be told apart with normal color vision, but look almost the same
+/// with a color vision deficiency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Confusion {
+    /// The color vision deficiency under which the colors merge.
+    pub vision: ColorVision,
+
+    /// The first color, as it is defined in the palette.
+    pub a: Color,
+
+    /// The second color, as it is defined in the palette. This is the background if a
+    /// palette color merges with the background.
+    pub b: Color,
+
+    /// CIEDE2000 difference between the simulated colors.
+    pub difference: f64,
+}
+
+/// The result of checking a palette with [`LegibilityCheck`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegibilityReport {
+    /// The background the palette was checked against.
+    pub background: Color,
+
+    /// Every color of the palette with its WCAG contrast ratio against the background.
+    pub contrast: Vec<(Color, f64)>,
+
+    /// The colors whose contrast ratio against the background is below the minimum, these
+    /// will nearly vanish.
+    pub low_contrast: Vec<Color>,
+
+    /// Pairs of colors, or a color and the background, that merge for color blind viewers.
+    pub confusions: Vec<Confusion>,
+}
+
+impl LegibilityReport {
+    /// True if no color has too low contrast and no colors merge for color blind viewers.
+    pub fn is_legible(&self) -> bool {
+        self.low_contrast.is_empty() && self.confusions.is_empty()
+    }
+}
+
+/**
+Checks how legible the colors of a palette are against its background.
+
+Example
+
+```
+use art::palette::{legibility::LegibilityCheck, palettes::Palettes};
+
+let (background, palette) = Palettes::orange_autumn();
+let report = LegibilityCheck::new()
+    .min_contrast(1.5)
+    .check(&background, palette.as_ref());
+
+for (color, contrast) in &report.contrast {
+    println!("{color}: {contrast:.2}:1");
+}
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LegibilityCheck {
+    min_contrast: f64,
+    min_difference: f64,
+}
+
+impl Default for LegibilityCheck {
+    fn default() -> Self {
+        LegibilityCheck {
+            min_contrast: 1.5,
+            min_difference: 8.0,
+        }
+    }
+}
+
+impl LegibilityCheck {
+    /// A check that flags colors with less than 1.5:1 contrast against the background, and
+    /// colors that are less than 8 CIEDE2000 apart when simulating color vision deficiency.
+    pub fn new() -> LegibilityCheck {
+        LegibilityCheck::default()
+    }
+
+    /// The lowest contrast ratio against the background before a color is flagged.
+    pub fn min_contrast(&mut self, ratio: f64) -> LegibilityCheck {
+        self.min_contrast = ratio;
+        *self
+    }
+
+    /// The smallest CIEDE2000 difference between two simulated colors before they are
+    /// considered merged.
+    pub fn min_difference(&mut self, difference: f64) -> LegibilityCheck {
+        self.min_difference = difference;
+        *self
+    }
+
+    /// Check the colors of `palette` against `background`.
+    pub fn check(&self, background: &Color, palette: &dyn Palette) -> LegibilityReport {
+        let colors = palette.colors();
+
+        let contrast = colors
+            .iter()
+            .map(|color| (*color, color.contrast_ratio(background)))
+            .collect::<Vec<(Color, f64)>>();
+
+        let low_contrast = contrast
+            .iter()
+            .filter(|(_, ratio)| *ratio < self.min_contrast)
+            .map(|(color, _)| *color)
+            .collect();
+
+        let mut pairs = vec![];
+        for (i, a) in colors.iter().enumerate() {
+            for b in colors.iter().skip(i + 1) {
+                pairs.push((*a, *b));
+            }
+            pairs.push((*a, *background));
+        }
+
+        let confusions = ColorVision::ALL
+            .iter()
+            .flat_map(|vision| {
+                pairs.iter().filter_map(move |(a, b)| {
+                    let difference = vision.simulate(a).delta_e_2000(&vision.simulate(b));
+                    let distinct = a.delta_e_2000(b) >= self.min_difference;
+
+                    (distinct && difference < self.min_difference).then_some(Confusion {
+                        vision: *vision,
+                        a: *a,
+                        b: *b,
+                        difference,
+                    })
+                })
+            })
+            .collect();
+
+        LegibilityReport {
+            background: *background,
+            contrast,
+            low_contrast,
+            confusions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::palette::{
+        color::Color, palettes::Palettes, simple_palette::SimplePalette,
+        weighted_palette::WeightedPalette,
+    };
+
+    use super::{ColorVision, LegibilityCheck};
+
+    #[test]
+    fn simulation_keeps_greys() {
+        let grey = Color::Hex("#808080");
+
+        for vision in ColorVision::ALL {
+            assert!(vision.simulate(&grey).delta_e_2000(&grey) < 1.0);
+        }
+    }
+
+    #[test]
+    fn flags_low_contrast() {
+        let palette = SimplePalette::new([Color::Hex("#fafafa"), Color::Hex("#000000")]);
+        let report = LegibilityCheck::new().check(&Color::Hex("#ffffff"), &palette);
+
+        assert_eq!(report.low_contrast, vec![Color::Hex("#fafafa")]);
+        assert_eq!(report.contrast[1].1.round(), 21.0);
+        assert!(!report.is_legible());
+    }
+
+    #[test]
+    fn flags_red_green_confusion() {
+        let palette =
+            WeightedPalette::new([(Color::Hex("#d42a2a"), 1), (Color::Hex("#6b8a00"), 1)]);
+        let report = LegibilityCheck::new().check(&Color::Hex("#ffffff"), &palette);
+
+        assert!(report.low_contrast.is_empty());
+        assert!(report.confusions.iter().any(|confusion| {
+            confusion.vision == ColorVision::Deuteranopia
+                && confusion.a == Color::Hex("#d42a2a")
+                && confusion.b == Color::Hex("#6b8a00")
+        }));
+    }
+
+    #[test]
+    fn predefined_palettes_have_contrast() {
+        for (background, palette) in [
+            Palettes::orange_autumn(),
+            Palettes::peaches_and_cream(),
+            Palettes::spring_break(),
+            Palettes::red_white_black(),
+            Palettes::wild(),
+        ] {
+            let report = LegibilityCheck::new()
+                .min_contrast(1.1)
+                .check(&background, palette.as_ref());
+
+            assert_eq!(report.contrast.len(), palette.colors().len());
+            assert_eq!(report.low_contrast, vec![], "{background}");
+        }
+    }
+}
@@ -1,12 +1,213 @@
-use self::color::Color;
+use rand::{distributions::WeightedIndex, prelude::Distribution, RngCore};
+
+use self::color::{Color, ColorDifference};
 
 pub mod color;
+pub mod color_map;
+pub mod extract;
+pub mod grade;
+pub mod gradient;
+pub mod harmony;
+pub mod jitter;
+pub mod legibility;
+mod named_colors;
+pub mod palette_file;
 pub mod palettes;
+mod pigment;
 pub mod regional_palette;
+pub mod registry;
+pub mod sampler;
 pub mod simple_palette;
+pub mod swatch;
 pub mod weighted_palette;
 
 pub trait Palette {
-    /// Get a random color from the given palette
-    fn get_random_color(&self) -> Option<Color>;
+    /// Get a random color from the given palette, drawn from `rng`. Pass a seeded rng, such
+    /// as `rand_chacha::ChaCha8Rng`, to get the same colors on every run.
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color>;
+
+    /// All the colors in the palette together with their weight, i.e how likely they are
+    /// to be picked compared to the other colors.
+    fn weighted_colors(&self) -> Vec<(Color, usize)>;
+
+    /// All the colors in the palette.
+    fn colors(&self) -> Vec<Color> {
+        self.weighted_colors()
+            .into_iter()
+            .map(|(color, _)| color)
+            .collect()
+    }
+
+    /**
+    Up to `count` different colors of the palette, picked by weight without putting picked
+    colors back. Returns fewer colors when the palette does not have `count` colors with a
+    weight above zero.
+
+    Example
+
+    ```
+    use art::palette::{color::Color, simple_palette::SimplePalette, Palette};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    let palette = SimplePalette::new([Color::Hex("#f00"), Color::Hex("#0f0"), Color::Hex("#00f")]);
+    let colors = palette.sample_distinct(2, &mut ChaCha8Rng::seed_from_u64(1));
+
+    assert_eq!(colors.len(), 2);
+    assert_ne!(colors[0], colors[1]);
+    ```
+    */
+    fn sample_distinct(&self, count: usize, rng: &mut dyn RngCore) -> Vec<Color> {
+        let mut colors = self.weighted_colors();
+        let mut picked = vec![];
+
+        while picked.len() < count {
+            let Ok(dist) = WeightedIndex::new(colors.iter().map(|(_, weight)| *weight)) else {
+                break;
+            };
+
+            let (color, _) = colors.swap_remove(dist.sample(rng));
+            colors.retain(|(other, _)| *other != color);
+            picked.push(color);
+        }
+
+        picked
+    }
+
+    /**
+    The color in the palette that looks the most like `color`. Good for snapping
+    arbitrary colors, such as pixels sampled from a photo, to the palette.
+
+    Example
+
+    ```
+    use art::palette::{
+        color::{Color, ColorDifference},
+        simple_palette::SimplePalette,
+        Palette,
+    };
+
+    let palette = SimplePalette::new([Color::Hex("#f00"), Color::Hex("#00f")]);
+    let nearest = palette.nearest_color(&Color::Hex("#e21"), ColorDifference::Ciede2000);
+
+    assert_eq!(nearest, Some(Color::Hex("#f00")));
+    ```
+    */
+    fn nearest_color(&self, color: &Color, difference: ColorDifference) -> Option<Color> {
+        self.colors().into_iter().min_by(|a, b| {
+            difference
+                .between(a, color)
+                .total_cmp(&difference.between(b, color))
+        })
+    }
+
+    /// The colors of the palette where colors that are less than `threshold` apart are
+    /// merged into one, keeping the color with the highest weight and adding up their
+    /// weights. With [`ColorDifference::Ciede2000`] a threshold of `2.0` merges colors
+    /// that are hard to tell apart.
+    fn deduplicate(&self, threshold: f64, difference: ColorDifference) -> Vec<(Color, usize)> {
+        let colors = self.weighted_colors();
+        let mut order = (0..colors.len()).collect::<Vec<usize>>();
+        order.sort_by(|a, b| colors[*b].1.cmp(&colors[*a].1));
+
+        let mut merged: Vec<(usize, usize)> = vec![];
+        for i in order {
+            let (color, weight) = colors[i];
+
+            match merged
+                .iter_mut()
+                .find(|(kept, _)| difference.between(&colors[*kept].0, &color) < threshold)
+            {
+                Some((_, total)) => *total += weight,
+                None => merged.push((i, weight)),
+            }
+        }
+
+        merged.sort_by_key(|(i, _)| *i);
+        merged
+            .into_iter()
+            .map(|(i, weight)| (colors[i].0, weight))
+            .collect()
+    }
+}
+
+impl<P: Palette + ?Sized> Palette for Box<P> {
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color> {
+        self.as_ref().get_random_color(rng)
+    }
+
+    fn weighted_colors(&self) -> Vec<(Color, usize)> {
+        self.as_ref().weighted_colors()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        color::{Color, ColorDifference},
+        weighted_palette::WeightedPalette,
+        Palette,
+    };
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn sample_distinct() {
+        let palette = WeightedPalette::new([
+            (Color::Hex("#181D31"), 5),
+            (Color::Hex("#E1B31E"), 1),
+            (Color::Hex("#181D31"), 1),
+            (Color::Hex("#FB5252"), 0),
+        ]);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+
+        let mut colors = palette.sample_distinct(5, &mut rng);
+        colors.sort_by_key(|color| color.to_hex());
+        assert_eq!(colors, vec![Color::Hex("#181D31"), Color::Hex("#E1B31E")]);
+
+        assert_eq!(
+            palette.sample_distinct(1, &mut ChaCha8Rng::seed_from_u64(9)),
+            palette.sample_distinct(1, &mut ChaCha8Rng::seed_from_u64(9))
+        );
+    }
+
+    #[test]
+    fn nearest_color() {
+        let palette = WeightedPalette::new([
+            (Color::Hex("#181D31"), 1),
+            (Color::Hex("#E1B31E"), 1),
+            (Color::Hex("#FB5252"), 1),
+        ]);
+
+        for difference in [ColorDifference::Ciede2000, ColorDifference::Oklab] {
+            assert_eq!(
+                palette.nearest_color(&Color::Hex("#000"), difference),
+                Some(Color::Hex("#181D31"))
+            );
+            assert_eq!(
+                palette.nearest_color(&Color::HSLa(45, 90.0, 60.0, 1.0), difference),
+                Some(Color::Hex("#E1B31E"))
+            );
+        }
+    }
+
+    #[test]
+    fn deduplicate() {
+        let palette = WeightedPalette::new([
+            (Color::Hex("#F0E9D2"), 2),
+            (Color::Hex("#E1B31E"), 1),
+            (Color::Hex("#E6DDC4"), 1),
+            (Color::Hex("#E1B31F"), 3),
+        ]);
+
+        assert_eq!(
+            palette.deduplicate(2.0, ColorDifference::Ciede2000),
+            vec![
+                (Color::Hex("#F0E9D2"), 2),
+                (Color::Hex("#E6DDC4"), 1),
+                (Color::Hex("#E1B31F"), 4)
+            ]
+        );
+        assert_eq!(palette.deduplicate(0.0, ColorDifference::Oklab).len(), 4);
+    }
 }
@@ -0,0 +1,651 @@
+use std::{fmt::Display, path::Path};
+
+use rand::{distributions::WeightedIndex, prelude::Distribution, RngCore};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    color::{Color, ParseColorError},
+    Palette,
+};
+
+/// The palette file formats that can be loaded and saved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteFormat {
+    /// GIMP palette, `.gpl`.
+    Gpl,
+
+    /// Adobe Swatch Exchange, `.ase`.
+    Ase,
+
+    /// The JSON schema of this crate, `.json`.
+    Json,
+
+    /// Hex colors separated by hyphens, like the URLs on coolors.co, any other extension.
+    HexList,
+}
+
+impl PaletteFormat {
+    /// The format for a file, decided by its extension.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> PaletteFormat {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("gpl") => PaletteFormat::Gpl,
+            Some("ase") => PaletteFormat::Ase,
+            Some("json") => PaletteFormat::Json,
+            _ => PaletteFormat::HexList,
+        }
+    }
+}
+
+/// Error returned when a palette file can not be read or written.
+#[derive(Debug)]
+pub enum PaletteFileError {
+    /// The file could not be read or written.
+    Io(std::io::Error),
+
+    /// The file is not valid for its format.
+    Invalid(String),
+
+    /// A color in the file could not be parsed.
+    Color(ParseColorError),
+
+    /// A JSON palette does not follow the schema.
+    Json(serde_json::Error),
+}
+
+impl Display for PaletteFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaletteFileError::Io(error) => write!(f, "could not access palette file: {error}"),
+            PaletteFileError::Invalid(reason) => write!(f, "invalid palette file: {reason}"),
+            PaletteFileError::Color(error) => write!(f, "invalid color in palette file: {error}"),
+            PaletteFileError::Json(error) => write!(f, "invalid JSON palette: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for PaletteFileError {}
+
+impl From<std::io::Error> for PaletteFileError {
+    fn from(error: std::io::Error) -> Self {
+        PaletteFileError::Io(error)
+    }
+}
+
+impl From<ParseColorError> for PaletteFileError {
+    fn from(error: ParseColorError) -> Self {
+        PaletteFileError::Color(error)
+    }
+}
+
+impl From<serde_json::Error> for PaletteFileError {
+    fn from(error: serde_json::Error) -> Self {
+        PaletteFileError::Json(error)
+    }
+}
+
+/**
+A palette that is loaded from, or saved to, a palette file. It has a name, an optional
+background and weighted colors, and can be used as a [`Palette`] right away.
+
+GIMP and Adobe palettes have no notion of weights or a background, so they are stored in
+the color names: a color named `background` is used as the background, and a name that
+ends with `(weight 3)` gives the color a weight of 3. Other colors get a weight of 1.
+
+The JSON schema looks like this, where colors are any CSS color and can also be plain
+strings when they have a weight of 1:
+
+```json
+{
+  "name": "orange autumn",
+  "background": "#181D31",
+  "colors": [
+    { "color": "#E1B31E", "weight": 3 },
+    "oklch(62.8% 0.2577 29.23)"
+  ]
+}
+```
+
+Example
+
+```no_run
+use art::palette::{palette_file::PaletteFile, palettes::Palettes, Palette};
+
+let (background, palette) = Palettes::orange_autumn();
+PaletteFile::from_palette("orange autumn", Some(background), palette.as_ref())
+    .save("orange-autumn.gpl")
+    .unwrap();
+
+let loaded = PaletteFile::load("orange-autumn.gpl").unwrap();
+let color = loaded.get_random_color(&mut rand::thread_rng());
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteFile {
+    name: String,
+    background: Option<Color>,
+    colors: Vec<(Color, usize)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonPalette {
+    #[serde(default)]
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    background: Option<String>,
+    colors: Vec<JsonColor>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonColor {
+    Plain(String),
+    Weighted {
+        color: String,
+        #[serde(default = "default_weight")]
+        weight: usize,
+    },
+}
+
+fn default_weight() -> usize {
+    1
+}
+
+impl PaletteFile {
+    /// Create a new palette with the given name, background and weighted colors.
+    pub fn new(name: &str, background: Option<Color>, colors: Vec<(Color, usize)>) -> PaletteFile {
+        PaletteFile {
+            name: name.to_string(),
+            background,
+            colors,
+        }
+    }
+
+    /// Create a palette from the colors and weights of any other palette, to be able to save it.
+    pub fn from_palette(
+        name: &str,
+        background: Option<Color>,
+        palette: &dyn Palette,
+    ) -> PaletteFile {
+        PaletteFile::new(name, background, palette.weighted_colors())
+    }
+
+    /// The name of the palette.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The background of the palette, if the file has one.
+    pub fn background(&self) -> Option<Color> {
+        self.background
+    }
+
+    /// Load a palette from disk, the format is decided by the file extension.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<PaletteFile, PaletteFileError> {
+        let bytes = std::fs::read(&path)?;
+        PaletteFile::parse(&bytes, PaletteFormat::from_path(path))
+    }
+
+    /// Save the palette to disk, the format is decided by the file extension.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), PaletteFileError> {
+        let bytes = self.encode(PaletteFormat::from_path(&path))?;
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Parse a palette from the contents of a palette file.
+    pub fn parse(bytes: &[u8], format: PaletteFormat) -> Result<PaletteFile, PaletteFileError> {
+        let text = || {
+            std::str::from_utf8(bytes)
+                .map_err(|_| PaletteFileError::Invalid(String::from("file is not valid UTF-8")))
+        };
+
+        match format {
+            PaletteFormat::Gpl => PaletteFile::parse_gpl(text()?),
+            PaletteFormat::Ase => PaletteFile::parse_ase(bytes),
+            PaletteFormat::Json => PaletteFile::parse_json(text()?),
+            PaletteFormat::HexList => PaletteFile::parse_hex_list(text()?),
+        }
+    }
+
+    /// The contents of a palette file in the given format.
+    pub fn encode(&self, format: PaletteFormat) -> Result<Vec<u8>, PaletteFileError> {
+        match format {
+            PaletteFormat::Gpl => Ok(self.to_gpl()?.into_bytes()),
+            PaletteFormat::Ase => self.to_ase(),
+            PaletteFormat::Json => Ok(self.to_json()?.into_bytes()),
+            PaletteFormat::HexList => Ok(self.to_hex_list()?.into_bytes()),
+        }
+    }
+
+    fn parse_gpl(text: &str) -> Result<PaletteFile, PaletteFileError> {
+        let mut lines = text.lines();
+
+        if lines.next().map(str::trim) != Some("GIMP Palette") {
+            return Err(PaletteFileError::Invalid(String::from(
+                "GIMP palettes start with \"GIMP Palette\"",
+            )));
+        }
+
+        let mut palette = PaletteFile::new("", None, vec![]);
+
+        for line in lines.map(str::trim) {
+            if let Some(name) = line.strip_prefix("Name:") {
+                palette.name = name.trim().to_string();
+                continue;
+            }
+
+            if line.is_empty() || line.starts_with('#') || line.starts_with("Columns:") {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let mut channel = || {
+                parts
+                    .next()
+                    .and_then(|channel| channel.parse::<u8>().ok())
+                    .ok_or_else(|| PaletteFileError::Invalid(format!("invalid color \"{line}\"")))
+            };
+            let color = Color::Rgba(channel()? as f64, channel()? as f64, channel()? as f64, 1.0);
+            let name = parts.collect::<Vec<&str>>().join(" ");
+
+            palette.add_named(color, &name);
+        }
+
+        Ok(palette)
+    }
+
+    fn parse_ase(bytes: &[u8]) -> Result<PaletteFile, PaletteFileError> {
+        let mut reader = AseReader { bytes, position: 0 };
+
+        if reader.take(4)? != b"ASEF" {
+            return Err(PaletteFileError::Invalid(String::from(
+                "Adobe swatch files start with \"ASEF\"",
+            )));
+        }
+
+        let _version = (reader.u16()?, reader.u16()?);
+        let blocks = reader.u32()?;
+        let mut palette = PaletteFile::new("", None, vec![]);
+
+        for _ in 0..blocks {
+            let kind = reader.u16()?;
+            let length = reader.u32()? as usize;
+            let block = reader.take(length)?;
+
+            match kind {
+                0x0001 => {
+                    let (color, name) = parse_ase_color(block)?;
+                    palette.add_named(color, &name);
+                }
+                0xC001 if palette.name.is_empty() => {
+                    palette.name = AseReader {
+                        bytes: block,
+                        position: 0,
+                    }
+                    .name()?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(palette)
+    }
+
+    fn parse_json(text: &str) -> Result<PaletteFile, PaletteFileError> {
+        let json: JsonPalette = serde_json::from_str(text)?;
+
+        let colors = json
+            .colors
+            .into_iter()
+            .map(|color| match color {
+                JsonColor::Plain(color) => Ok((color.parse()?, 1)),
+                JsonColor::Weighted { color, weight } => Ok((color.parse()?, weight)),
+            })
+            .collect::<Result<Vec<(Color, usize)>, ParseColorError>>()?;
+
+        let background = json.background.map(|color| color.parse()).transpose()?;
+
+        Ok(PaletteFile::new(&json.name, background, colors))
+    }
+
+    fn parse_hex_list(text: &str) -> Result<PaletteFile, PaletteFileError> {
+        let list = text.trim().rsplit('/').next().unwrap_or_default();
+
+        let colors = list
+            .split(|c: char| c == '-' || c == ',' || c.is_whitespace())
+            .filter(|color| !color.is_empty())
+            .map(|color| {
+                format!("#{}", color.trim_start_matches('#'))
+                    .parse()
+                    .map(|color| (color, 1))
+            })
+            .collect::<Result<Vec<(Color, usize)>, ParseColorError>>()?;
+
+        Ok(PaletteFile::new("", None, colors))
+    }
+
+    /// The palette as a GIMP palette, or an error if a color is a [`Color::Hex`] that does
+    /// not hold a valid hex color.
+    pub fn to_gpl(&self) -> Result<String, PaletteFileError> {
+        let mut gpl = format!("GIMP Palette\nName: {}\nColumns: 0\n#\n", self.name);
+
+        for (color, name) in self.named_colors()? {
+            let (r, g, b, _) = color.try_srgba()?;
+            let [r, g, b] =
+                [r, g, b].map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8);
+            gpl.push_str(&format!("{r:3} {g:3} {b:3}\t{name}\n"));
+        }
+
+        Ok(gpl)
+    }
+
+    /// The palette as an Adobe swatch exchange file, with every color in RGB, or an error
+    /// if a color is a [`Color::Hex`] that does not hold a valid hex color.
+    pub fn to_ase(&self) -> Result<Vec<u8>, PaletteFileError> {
+        let colors = self.named_colors()?;
+        let mut ase = b"ASEF".to_vec();
+        ase.extend(1u16.to_be_bytes());
+        ase.extend(0u16.to_be_bytes());
+        ase.extend((colors.len() as u32).to_be_bytes());
+
+        for (color, name) in colors {
+            let name = name.encode_utf16().chain([0]).collect::<Vec<u16>>();
+            let (r, g, b, _) = color.try_srgba()?;
+
+            let mut block = (name.len() as u16).to_be_bytes().to_vec();
+            block.extend(name.iter().flat_map(|c| c.to_be_bytes()));
+            block.extend(b"RGB ");
+            for channel in [r, g, b] {
+                block.extend((channel.clamp(0.0, 1.0) as f32).to_be_bytes());
+            }
+            block.extend(2u16.to_be_bytes());
+
+            ase.extend(0x0001u16.to_be_bytes());
+            ase.extend((block.len() as u32).to_be_bytes());
+            ase.extend(block);
+        }
+
+        Ok(ase)
+    }
+
+    /// The palette in the JSON schema of this crate.
+    pub fn to_json(&self) -> Result<String, PaletteFileError> {
+        let json = JsonPalette {
+            name: self.name.clone(),
+            background: self.background.map(|color| color.to_string()),
+            colors: self
+                .colors
+                .iter()
+                .map(|(color, weight)| JsonColor::Weighted {
+                    color: color.to_string(),
+                    weight: *weight,
+                })
+                .collect(),
+        };
+
+        Ok(serde_json::to_string_pretty(&json)?)
+    }
+
+    /// The colors of the palette as hex values separated by hyphens, without the background
+    /// and weights. Fails if a color is a [`Color::Hex`] that does not hold a valid hex color.
+    pub fn to_hex_list(&self) -> Result<String, PaletteFileError> {
+        Ok(self
+            .colors
+            .iter()
+            .map(|(color, _)| Ok(color.try_to_hex()?.trim_start_matches('#').to_string()))
+            .collect::<Result<Vec<String>, ParseColorError>>()?
+            .join("-"))
+    }
+
+    /// Add a color where the name can mark it as the background or carry its weight.
+    fn add_named(&mut self, color: Color, name: &str) {
+        let name = name.trim();
+
+        if name.eq_ignore_ascii_case("background") {
+            self.background = Some(color);
+            return;
+        }
+
+        let weight = name
+            .strip_suffix(')')
+            .and_then(|name| name.rsplit_once("(weight "))
+            .and_then(|(_, weight)| weight.trim().parse().ok())
+            .unwrap_or(1);
+
+        self.colors.push((color, weight));
+    }
+
+    /// The background and colors with names that carry the background and the weights.
+    fn named_colors(&self) -> Result<Vec<(Color, String)>, ParseColorError> {
+        self.background
+            .map(|color| Ok((color, String::from("background"))))
+            .into_iter()
+            .chain(self.colors.iter().map(|(color, weight)| {
+                Ok((*color, format!("{} (weight {weight})", color.try_to_hex()?)))
+            }))
+            .collect()
+    }
+}
+
+impl Palette for PaletteFile {
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color> {
+        let dist = WeightedIndex::new(self.colors.iter().map(|(_, weight)| *weight)).ok()?;
+        Some(self.colors[dist.sample(rng)].0)
+    }
+
+    fn weighted_colors(&self) -> Vec<(Color, usize)> {
+        self.colors.clone()
+    }
+}
+
+/// Reads the big endian values of an Adobe swatch exchange file.
+struct AseReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> AseReader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], PaletteFileError> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + length)
+            .ok_or_else(|| PaletteFileError::Invalid(String::from("unexpected end of file")))?;
+
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, PaletteFileError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, PaletteFileError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn f32(&mut self) -> Result<f64, PaletteFileError> {
+        Ok(f32::from_bits(self.u32()?) as f64)
+    }
+
+    /// A UTF-16 name prefixed with its length, including the null terminator.
+    fn name(&mut self) -> Result<String, PaletteFileError> {
+        let length = self.u16()? as usize;
+        let name = (0..length)
+            .map(|_| self.u16())
+            .collect::<Result<Vec<u16>, PaletteFileError>>()?;
+
+        Ok(String::from_utf16_lossy(&name)
+            .trim_end_matches('\0')
+            .to_string())
+    }
+}
+
+fn parse_ase_color(block: &[u8]) -> Result<(Color, String), PaletteFileError> {
+    let mut reader = AseReader {
+        bytes: block,
+        position: 0,
+    };
+
+    let name = reader.name()?;
+    let model = reader.take(4)?;
+
+    let (r, g, b) = match model {
+        b"RGB " => (reader.f32()?, reader.f32()?, reader.f32()?),
+        b"Gray" => {
+            let gray = reader.f32()?;
+            (gray, gray, gray)
+        }
+        b"CMYK" => {
+            let (c, m, y, k) = (reader.f32()?, reader.f32()?, reader.f32()?, reader.f32()?);
+            (
+                (1.0 - c) * (1.0 - k),
+                (1.0 - m) * (1.0 - k),
+                (1.0 - y) * (1.0 - k),
+            )
+        }
+        _ => {
+            return Err(PaletteFileError::Invalid(format!(
+                "unsupported color model \"{}\"",
+                String::from_utf8_lossy(model).trim()
+            )))
+        }
+    };
+
+    Ok((Color::Rgba(r * 255.0, g * 255.0, b * 255.0, 1.0), name))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::palette::{color::Color, palettes::Palettes, Palette};
+
+    use super::{PaletteFile, PaletteFileError, PaletteFormat};
+
+    fn palette() -> PaletteFile {
+        let (background, palette) = Palettes::orange_autumn();
+        PaletteFile::from_palette("orange autumn", Some(background), palette.as_ref())
+    }
+
+    fn hex(palette: &PaletteFile) -> (Option<String>, Vec<(String, usize)>) {
+        (
+            palette.background().map(|color| color.to_hex()),
+            palette
+                .weighted_colors()
+                .iter()
+                .map(|(color, weight)| (color.to_hex(), *weight))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn round_trip_all_formats() {
+        let palette = palette();
+
+        for format in [PaletteFormat::Gpl, PaletteFormat::Ase, PaletteFormat::Json] {
+            let bytes = palette.encode(format).unwrap();
+            let loaded = PaletteFile::parse(&bytes, format).unwrap();
+
+            assert_eq!(hex(&loaded), hex(&palette), "{format:?}");
+            assert!(loaded.get_random_color(&mut rand::thread_rng()).is_some());
+        }
+
+        let loaded = PaletteFile::parse(
+            &palette.encode(PaletteFormat::HexList).unwrap(),
+            PaletteFormat::HexList,
+        )
+        .unwrap();
+        assert_eq!(
+            hex(&loaded).1,
+            hex(&palette)
+                .1
+                .into_iter()
+                .map(|(color, _)| (color, 1))
+                .collect::<Vec<(String, usize)>>()
+        );
+    }
+
+    #[test]
+    fn parse_gpl() {
+        let gpl = "GIMP Palette\nName: Test\nColumns: 2\n# comment\n255   0   0\tRed\n  0   0 255 Untitled (weight 4)\n";
+        let palette = PaletteFile::parse(gpl.as_bytes(), PaletteFormat::Gpl).unwrap();
+
+        assert_eq!(palette.name(), "Test");
+        assert_eq!(
+            hex(&palette).1,
+            vec![(String::from("#ff0000"), 1), (String::from("#0000ff"), 4)]
+        );
+
+        assert!(matches!(
+            PaletteFile::parse(b"255 0 0", PaletteFormat::Gpl),
+            Err(PaletteFileError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn parse_json() {
+        let json = r##"{ "background": "black", "colors": ["#f00", { "color": "hsl(240, 100%, 50%)", "weight": 2 }] }"##;
+        let palette = PaletteFile::parse(json.as_bytes(), PaletteFormat::Json).unwrap();
+
+        assert_eq!(palette.background(), Some(Color::Rgba(0.0, 0.0, 0.0, 1.0)));
+        assert_eq!(
+            hex(&palette).1,
+            vec![(String::from("#ff0000"), 1), (String::from("#0000ff"), 2)]
+        );
+
+        assert!(matches!(
+            PaletteFile::parse(br#"{ "colors": ["nope"] }"#, PaletteFormat::Json),
+            Err(PaletteFileError::Color(_))
+        ));
+    }
+
+    #[test]
+    fn parse_hex_list() {
+        let list = "https://coolors.co/181d31-e1b31e-678983";
+        let palette = PaletteFile::parse(list.as_bytes(), PaletteFormat::HexList).unwrap();
+
+        assert_eq!(palette.colors().len(), 3);
+        assert_eq!(palette.colors()[1].to_hex(), "#e1b31e");
+        assert_eq!(palette.to_hex_list().unwrap(), "181d31-e1b31e-678983");
+    }
+
+    #[test]
+    fn invalid_hex_color() {
+        let palette = PaletteFile::new("broken", None, vec![(Color::Hex("#ff00zz"), 1)]);
+
+        for format in [
+            PaletteFormat::Gpl,
+            PaletteFormat::Ase,
+            PaletteFormat::HexList,
+        ] {
+            assert!(matches!(
+                palette.encode(format),
+                Err(PaletteFileError::Color(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(PaletteFormat::from_path("a/b.GPL"), PaletteFormat::Gpl);
+        assert_eq!(PaletteFormat::from_path("b.ase"), PaletteFormat::Ase);
+        assert_eq!(PaletteFormat::from_path("b.json"), PaletteFormat::Json);
+        assert_eq!(PaletteFormat::from_path("b.txt"), PaletteFormat::HexList);
+    }
+
+    #[test]
+    fn truncated_ase() {
+        let ase = palette().to_ase().unwrap();
+
+        assert!(matches!(
+            PaletteFile::parse(&ase[..ase.len() - 3], PaletteFormat::Ase),
+            Err(PaletteFileError::Invalid(_))
+        ));
+    }
+}
@@ -2,11 +2,53 @@ use super::{
     color::Color, simple_palette::SimplePalette, weighted_palette::WeightedPalette, Palette,
 };
 
+/// The name, a short description and the constructor of a predefined palette.
+pub type PaletteDefinition = (
+    &'static str,
+    &'static str,
+    fn() -> (Color, Box<dyn Palette>),
+);
+
+/// Every predefined palette, new palettes only need to be added here to show up in the
+/// [`PaletteRegistry`](super::registry::PaletteRegistry) and the swatch sheet.
+const ALL: [PaletteDefinition; 5] = [
+    (
+        "orange_autumn",
+        "Vibrant orange, red and off-white against a dark background",
+        Palettes::orange_autumn,
+    ),
+    (
+        "peaches_and_cream",
+        "Pastelly pinks, orange and red",
+        Palettes::peaches_and_cream,
+    ),
+    (
+        "spring_break",
+        "Blue, white and yellow",
+        Palettes::spring_break,
+    ),
+    (
+        "red_white_black",
+        "Black and white against red",
+        Palettes::red_white_black,
+    ),
+    (
+        "wild",
+        "Lime, orange, cream and brick red against a dark brown background",
+        Palettes::wild,
+    ),
+];
+
 /// A list of predefined color palettes
 #[derive(Debug)]
 pub struct Palettes;
 
 impl Palettes {
+    /// All the predefined palettes with their names and descriptions.
+    pub fn all() -> &'static [PaletteDefinition] {
+        &ALL
+    }
+
     /// Vibrant Orange, red, off-white against a dark background
     pub fn orange_autumn() -> (Color, Box<dyn Palette>) {
         let background = Color::Hex("#181D31");
@@ -59,6 +101,7 @@ impl Palettes {
         (background, Box::new(colors))
     }
 
+    /// Lime, orange, cream and brick red against a dark brown background
     pub fn wild() -> (Color, Box<dyn Palette>) {
         let background = Color::Hex("#1d1a13");
         let colors = WeightedPalette::new([
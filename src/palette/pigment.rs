@@ -0,0 +1,257 @@
+//! Subtractive, paint like, color mixing. Colors are turned into reflectance spectra, the
+//! spectra are mixed with the Kubelka-Munk model, and the result is turned back into a
+//! color. This is the same approach as spectral.js and Mixbox, with a coarser spectrum.
+
+use std::sync::OnceLock;
+
+use super::color::{linear_to_srgb, srgb_to_linear};
+
+/// Number of wavelength bands, evenly spread from 400nm to 700nm.
+const BANDS: usize = 12;
+
+/// Number of basis spectra: white, cyan, magenta, yellow, red, green and blue.
+const BASES: usize = 7;
+
+/// The smallest reflectance, black pigment would otherwise absorb infinitely.
+const MIN_REFLECTANCE: f64 = 1e-4;
+
+/// How many bands a basis spectrum takes to go from absorbing to reflecting.
+const WIDTH: f64 = 0.5;
+
+/// Reflectance of every basis in the bands it absorbs, no real pigment absorbs everything.
+const FLOOR: f64 = 0.03;
+
+/// The bands where the basis spectra start or stop reflecting.
+const CYAN: f64 = 8.0;
+const MAGENTA: (f64, f64) = (4.0, 6.5);
+const YELLOW: f64 = 2.5;
+const RED: f64 = 7.5;
+const GREEN: (f64, f64) = (3.5, 7.5);
+const BLUE: f64 = 5.5;
+
+/// Like most real pigments, red reflects some violet and blue reflects some deep red,
+/// which is what makes red and blue mix to purple rather than to a muddy grey.
+const RED_TAIL: f64 = 0.2;
+const BLUE_TAIL: f64 = 0.1;
+
+type Spectrum = [f64; BANDS];
+
+/// Basis spectra and the linear RGB of each basis.
+struct Model {
+    bases: [Spectrum; BASES],
+    to_rgb: [Spectrum; 3],
+}
+
+/// Mix two sRGB colors, with channels in `0..=1`, like paint. `t` is the concentration of
+/// `b`, from `0.0` for only `a` to `1.0` for only `b`.
+pub(crate) fn mix(a: [f64; 3], b: [f64; 3], t: f64) -> [f64; 3] {
+    let t = t.clamp(0.0, 1.0);
+    let (a, b) = (spectrum(a), spectrum(b));
+
+    let mut mixed = [0.0; BANDS];
+    for band in 0..BANDS {
+        let absorption = (1.0 - t) * absorption(a[band]) + t * absorption(b[band]);
+        mixed[band] = reflectance(absorption);
+    }
+
+    model()
+        .to_rgb
+        .map(|row| linear_to_srgb(dot(&row, &mixed).clamp(0.0, 1.0)))
+}
+
+/// Kubelka-Munk ratio of absorption to scattering for a reflectance.
+fn absorption(reflectance: f64) -> f64 {
+    let reflectance = reflectance.clamp(MIN_REFLECTANCE, 1.0);
+    (1.0 - reflectance).powi(2) / (2.0 * reflectance)
+}
+
+/// Inverse of [`absorption`].
+fn reflectance(absorption: f64) -> f64 {
+    1.0 + absorption - (absorption.powi(2) + 2.0 * absorption).sqrt()
+}
+
+/// Split an sRGB color into white, one secondary and one primary color, and add up their
+/// spectra. Unlike splitting into red, green and blue only, this keeps yellow pigment
+/// reflecting green light, which is what makes blue and yellow mix to green.
+fn spectrum(rgb: [f64; 3]) -> Spectrum {
+    let [mut r, mut g, mut b] = rgb.map(|channel| srgb_to_linear(channel.clamp(0.0, 1.0)));
+
+    let white = r.min(g).min(b);
+    r -= white;
+    g -= white;
+    b -= white;
+
+    let weights = [
+        white,
+        g.min(b),
+        r.min(b),
+        r.min(g),
+        (r - b).min(r - g).max(0.0),
+        (g - b).min(g - r).max(0.0),
+        (b - g).min(b - r).max(0.0),
+    ];
+
+    let model = model();
+    let mut spectrum = [0.0; BANDS];
+    for (basis, weight) in model.bases.iter().zip(weights) {
+        for band in 0..BANDS {
+            spectrum[band] += basis[band] * weight;
+        }
+    }
+
+    spectrum
+}
+
+fn model() -> &'static Model {
+    static MODEL: OnceLock<Model> = OnceLock::new();
+
+    MODEL.get_or_init(|| {
+        let edge = |center: f64| -> Spectrum {
+            std::array::from_fn(|band| 1.0 / (1.0 + (-(band as f64 - center) / WIDTH).exp()))
+        };
+        let below = |center: f64| edge(center).map(|value| 1.0 - value);
+
+        let bases: [Spectrum; BASES] = [
+            [1.0; BANDS],
+            below(CYAN),
+            std::array::from_fn(|band| (below(MAGENTA.0)[band] + edge(MAGENTA.1)[band]).min(1.0)),
+            edge(YELLOW),
+            std::array::from_fn(|band| edge(RED)[band].max(RED_TAIL * below(1.0)[band])),
+            std::array::from_fn(|band| edge(GREEN.0)[band] * below(GREEN.1)[band]),
+            std::array::from_fn(|band| below(BLUE)[band].max(BLUE_TAIL * edge(10.0)[band])),
+        ]
+        .map(|basis| basis.map(|value| FLOOR + (1.0 - FLOOR) * value));
+        let colors: [[f64; 3]; BASES] = [
+            [1.0, 1.0, 1.0],
+            [0.0, 1.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [1.0, 1.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ];
+
+        // Least squares projection from spectra back to RGB that maps every basis spectrum
+        // exactly onto its color: colors * (basesᵀ * bases)⁻¹ * basesᵀ.
+        let mut gram = [[0.0; BASES]; BASES];
+        for i in 0..BASES {
+            for j in 0..BASES {
+                gram[i][j] = dot(&bases[i], &bases[j]);
+            }
+        }
+        let inverse = invert(gram);
+
+        let to_rgb = std::array::from_fn(|channel| {
+            let mut row = [0.0; BANDS];
+            for i in 0..BASES {
+                for j in 0..BASES {
+                    let weight = colors[i][channel] * inverse[i][j];
+                    for band in 0..BANDS {
+                        row[band] += weight * bases[j][band];
+                    }
+                }
+            }
+            row
+        });
+
+        Model { bases, to_rgb }
+    })
+}
+
+fn dot(a: &Spectrum, b: &Spectrum) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Gauss-Jordan elimination with partial pivoting.
+fn invert(mut matrix: [[f64; BASES]; BASES]) -> [[f64; BASES]; BASES] {
+    let mut inverse: [[f64; BASES]; BASES] =
+        std::array::from_fn(|i| std::array::from_fn(|j| if i == j { 1.0 } else { 0.0 }));
+
+    for column in 0..BASES {
+        let pivot = (column..BASES)
+            .max_by(|a, b| {
+                matrix[*a][column]
+                    .abs()
+                    .total_cmp(&matrix[*b][column].abs())
+            })
+            .unwrap_or(column);
+        matrix.swap(column, pivot);
+        inverse.swap(column, pivot);
+
+        let scale = matrix[column][column];
+        for j in 0..BASES {
+            matrix[column][j] /= scale;
+            inverse[column][j] /= scale;
+        }
+
+        for row in 0..BASES {
+            if row != column {
+                let factor = matrix[row][column];
+                for j in 0..BASES {
+                    matrix[row][j] -= factor * matrix[column][j];
+                    inverse[row][j] -= factor * inverse[column][j];
+                }
+            }
+        }
+    }
+
+    inverse
+}
+
+#[cfg(test)]
+mod test {
+    use super::mix;
+
+    fn rgb(color: [f64; 3]) -> [u8; 3] {
+        color.map(|channel| (channel * 255.0).round() as u8)
+    }
+
+    fn assert_close(a: [f64; 3], b: [f64; 3]) {
+        assert!(
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() < 0.005),
+            "{:?} != {:?}",
+            rgb(a),
+            rgb(b)
+        );
+    }
+
+    #[test]
+    fn unmixed_colors_are_unchanged() {
+        for color in [
+            [0.3, 0.6, 0.2],
+            [1.0, 0.0, 0.0],
+            [0.9, 0.8, 0.1],
+            [0.0, 0.0, 0.0],
+        ] {
+            assert_close(mix(color, [0.0, 0.0, 1.0], 0.0), color);
+            assert_close(mix([0.0, 0.0, 1.0], color, 1.0), color);
+            assert_close(mix(color, color, 0.5), color);
+        }
+    }
+
+    #[test]
+    fn primaries() {
+        let [r, g, b] = rgb(mix([0.0, 0.0, 1.0], [1.0, 1.0, 0.0], 0.5));
+        assert!(g > r && g > b, "blue and yellow {:?}", [r, g, b]);
+
+        let [r, g, b] = rgb(mix([1.0, 0.0, 0.0], [1.0, 1.0, 0.0], 0.5));
+        assert!(
+            r == 255 && g > 50 && b < 20,
+            "red and yellow {:?}",
+            [r, g, b]
+        );
+
+        let [r, g, b] = rgb(mix([1.0, 0.0, 0.0], [0.0, 0.0, 1.0], 0.5));
+        assert!(r > g && b > g && r < 200, "red and blue {:?}", [r, g, b]);
+    }
+
+    #[test]
+    fn white_and_black() {
+        let [r, g, b] = rgb(mix([1.0, 1.0, 1.0], [0.0, 0.0, 1.0], 0.5));
+        assert!(b == 255 && r > 40 && g > 40, "{:?}", [r, g, b]);
+
+        // A little black pigment goes a long way.
+        let [r, g, b] = rgb(mix([1.0, 1.0, 1.0], [0.0, 0.0, 0.0], 0.2));
+        assert!(r == g && g == b && r < 64, "{:?}", [r, g, b]);
+    }
+}
@@ -1,60 +1,318 @@
+use noise::{NoiseFn, Perlin};
+use rand::{distributions::WeightedIndex, prelude::Distribution, RngCore};
+
+use crate::shapes::{point::Point, rectangle::Rectangle};
+
 use super::{color::Color, Palette};
-use rand::{distributions::WeightedIndex, prelude::Distribution};
 
-/// A set of colors where one can be chosen randomly but biased by a given weight
-#[derive(Debug)]
-pub struct WeightedPalette<const N: usize> {
-    colors: [(Color, usize); N],
-}
+/// How the canvas is split into regions, every region gets its own palette.
+#[derive(Debug, Clone)]
+pub enum Regions {
+    /// Every rectangle is a region. Points outside all rectangles belong to the closest one,
+    /// the rotation of the rectangles is ignored.
+    Rectangles(Vec<Rectangle>),
+
+    /// Every seed point is a region, which covers the points that are closer to it than to
+    /// any other seed.
+    Voronoi(Vec<Point>),
 
-impl<const N: usize> WeightedPalette<N> {
-    /**
-     Create a new weighted color palette
+    /// Perlin noise split into one band per palette, which gives organic, blobby regions.
+    /// `scale` is the noise frequency, i.e. how many noise cells fit in one unit of the
+    /// canvas, so a value around `0.002` gives a few regions on a 1000 wide canvas.
+    Noise { seed: u32, scale: f64 },
+}
 
-     Example
+/**
+A palette where the color depends on where it is used. The canvas is split into
+[`Regions`] and every region gets one of the palettes, in order, starting over when there
+are more regions than palettes. Near the border of two regions both palettes are picked,
+with a chance that depends on how close the point is to the other region, so colors
+dissolve into each other instead of forming a hard edge.
 
-     ```
+Example
 
-     use generative_art::{palette::{Palette, color::Color, weighted_palette::WeightedPalette}};
+```
+use art::{
+    palette::{
+        color::Color,
+        regional_palette::{RegionalPalette, Regions},
+        simple_palette::SimplePalette,
+    },
+    shapes::point::Point,
+};
 
-     let palette = WeightedPalette::new([
-        (Color::Hex("#f00"), 1),
-        (Color::Hex("#0f0"), 5),
-        (Color::Hex("#00f"), 1)
-     ]);
+let palette = RegionalPalette::new(
+    Regions::Voronoi(vec![Point(100.0, 100.0), Point(900.0, 900.0)]),
+    vec![
+        Box::new(SimplePalette::new([Color::Hex("#181D31"), Color::Hex("#678983")])),
+        Box::new(SimplePalette::new([Color::Hex("#E1B31E"), Color::Hex("#F0E9D2")])),
+    ],
+)
+.blend(50.0);
 
-     if let Some(random_color) = palette.get_random_color() {
-     // random_color has 5 times as high of a chance to be picked as
-     // either red or blue.
-     }
-     ```
+let color = palette.get_color_at(&Point(400.0, 300.0), &mut rand::thread_rng());
+```
+*/
+pub struct RegionalPalette {
+    regions: Regions,
+    palettes: Vec<Box<dyn Palette>>,
+    blend: f64,
+    noise: Perlin,
+}
 
-    */
-    pub fn new(colors: [(Color, usize); N]) -> Self {
-        WeightedPalette { colors }
+impl std::fmt::Debug for RegionalPalette {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegionalPalette")
+            .field("regions", &self.regions)
+            .field(
+                "palettes",
+                &self
+                    .palettes
+                    .iter()
+                    .map(|palette| palette.weighted_colors())
+                    .collect::<Vec<Vec<(Color, usize)>>>(),
+            )
+            .field("blend", &self.blend)
+            .finish()
     }
 }
 
-impl<const N: usize> Palette for WeightedPalette<N> {
-    fn get_random_color(&self) -> Option<Color> {
-        let mut rng = rand::thread_rng();
-        let weights = self
-            .colors
+impl RegionalPalette {
+    /// Create a regional palette with hard borders between the regions.
+    pub fn new(regions: Regions, palettes: Vec<Box<dyn Palette>>) -> RegionalPalette {
+        let seed = match regions {
+            Regions::Noise { seed, .. } => seed,
+            _ => Perlin::DEFAULT_SEED,
+        };
+
+        RegionalPalette {
+            regions,
+            palettes,
+            blend: 0.0,
+            noise: Perlin::new(seed),
+        }
+    }
+
+    /// How wide the soft border between two regions is, in canvas units. For noise regions
+    /// the width is approximate, since the noise changes faster in some places than others.
+    pub fn blend(mut self, width: f64) -> RegionalPalette {
+        self.blend = width.max(0.0);
+        self
+    }
+
+    /// The index of the palette of the region that `point` belongs to.
+    pub fn region_at(&self, point: &Point) -> Option<usize> {
+        let distances = self.distances(point);
+
+        distances
             .iter()
-            .map(|color| color.1)
-            .collect::<Vec<usize>>();
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i % self.palettes.len().max(1))
+            .filter(|_| !self.palettes.is_empty())
+    }
 
-        let dist = if let Ok(dist) = WeightedIndex::new(&weights) {
-            dist
-        } else {
-            return None;
+    /// The palettes that can be picked at `point`, with how likely they are to be picked.
+    /// Away from the borders this is only the palette of the region the point is in.
+    pub fn influences_at(&self, point: &Point) -> Vec<(usize, f64)> {
+        if self.palettes.is_empty() {
+            return vec![];
+        }
+
+        let distances = self.distances(point);
+        let Some(closest) = distances.iter().copied().reduce(f64::min) else {
+            return vec![];
         };
 
-        let i = dist.sample(&mut rng);
+        let mut influences: Vec<(usize, f64)> = vec![];
+        for (region, distance) in distances.iter().enumerate() {
+            let influence = if self.blend > 0.0 {
+                (1.0 - (distance - closest) / self.blend).clamp(0.0, 1.0)
+            } else if *distance == closest {
+                1.0
+            } else {
+                0.0
+            };
 
-        match self.colors.len() {
-            0 => None,
-            _ => Some(self.colors[i].0),
+            if influence <= 0.0 {
+                continue;
+            }
+
+            let palette = region % self.palettes.len();
+            match influences.iter_mut().find(|(i, _)| *i == palette) {
+                Some((_, total)) => *total = total.max(influence),
+                None => influences.push((palette, influence)),
+            }
         }
+
+        let total = influences
+            .iter()
+            .map(|(_, influence)| influence)
+            .sum::<f64>();
+        influences
+            .into_iter()
+            .map(|(palette, influence)| (palette, influence / total))
+            .collect()
+    }
+
+    /// A random color for `point`, from the palette of its region or, near a border, from
+    /// one of the neighboring palettes.
+    pub fn get_color_at(&self, point: &Point, rng: &mut dyn RngCore) -> Option<Color> {
+        let influences = self.influences_at(point);
+        let dist = WeightedIndex::new(influences.iter().map(|(_, influence)| *influence)).ok()?;
+
+        self.palettes[influences[dist.sample(rng)].0].get_random_color(rng)
+    }
+
+    /// How far `point` is from every region, negative when the point is inside the region.
+    fn distances(&self, point: &Point) -> Vec<f64> {
+        match &self.regions {
+            Regions::Rectangles(rectangles) => rectangles
+                .iter()
+                .map(|rectangle| signed_distance(rectangle, point))
+                .collect(),
+            Regions::Voronoi(seeds) => seeds.iter().map(|seed| seed.distance_to(point)).collect(),
+            Regions::Noise { scale, .. } => {
+                let bands = self.palettes.len();
+                let value = self.noise.get([point.0 * scale, point.1 * scale]);
+                let position = ((value + 1.0) / 2.0).clamp(0.0, 1.0) * bands as f64;
+                // The position moves roughly `bands * scale / 2` per unit of the canvas.
+                let to_canvas = 2.0 / (bands as f64 * scale).max(f64::EPSILON);
+
+                (0..bands)
+                    .map(|band| ((position - (band as f64 + 0.5)).abs() - 0.5) * to_canvas)
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Palette for RegionalPalette {
+    /// A random color from any of the palettes, regardless of position.
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color> {
+        let colors = self.weighted_colors();
+        let dist = WeightedIndex::new(colors.iter().map(|(_, weight)| *weight)).ok()?;
+
+        Some(colors[dist.sample(rng)].0)
+    }
+
+    fn weighted_colors(&self) -> Vec<(Color, usize)> {
+        self.palettes
+            .iter()
+            .flat_map(|palette| palette.weighted_colors())
+            .collect()
+    }
+}
+
+/// Distance from `point` to the edge of `rectangle`, negative inside the rectangle.
+fn signed_distance(rectangle: &Rectangle, point: &Point) -> f64 {
+    let (x, y) = (rectangle.x_range(), rectangle.y_range());
+    let dx = (x.start - point.0).max(point.0 - x.end);
+    let dy = (y.start - point.1).max(point.1 - y.end);
+
+    dx.max(0.0).hypot(dy.max(0.0)) + dx.max(dy).min(0.0)
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        palette::{color::Color, simple_palette::SimplePalette, Palette},
+        shapes::{point::Point, rectangle::Rectangle},
+    };
+
+    use super::{RegionalPalette, Regions};
+
+    fn palettes() -> Vec<Box<dyn Palette>> {
+        vec![
+            Box::new(SimplePalette::new([Color::Hex("#f00")])),
+            Box::new(SimplePalette::new([Color::Hex("#00f")])),
+        ]
+    }
+
+    #[test]
+    fn voronoi_regions() {
+        let palette = RegionalPalette::new(
+            Regions::Voronoi(vec![Point(0.0, 0.0), Point(100.0, 0.0), Point(0.0, 100.0)]),
+            palettes(),
+        );
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+        assert_eq!(palette.region_at(&Point(10.0, 10.0)), Some(0));
+        assert_eq!(palette.region_at(&Point(90.0, 10.0)), Some(1));
+        // More regions than palettes, the third region uses the first palette again.
+        assert_eq!(palette.region_at(&Point(10.0, 90.0)), Some(0));
+        assert_eq!(
+            palette.get_color_at(&Point(90.0, 10.0), &mut rng),
+            Some(Color::Hex("#00f"))
+        );
+    }
+
+    #[test]
+    fn rectangles_blend_at_border() {
+        let palette = RegionalPalette::new(
+            Regions::Rectangles(vec![
+                Rectangle::new(Point(0.0, 0.0), 50.0, 100.0),
+                Rectangle::new(Point(50.0, 0.0), 50.0, 100.0),
+            ]),
+            palettes(),
+        )
+        .blend(20.0);
+
+        assert_eq!(palette.influences_at(&Point(10.0, 50.0)), vec![(0, 1.0)]);
+        assert_eq!(
+            palette.influences_at(&Point(50.0, 50.0)),
+            vec![(0, 0.5), (1, 0.5)]
+        );
+
+        let near_border = palette.influences_at(&Point(45.0, 50.0));
+        assert!(near_border[0].1 > 0.5 && near_border[1].1 > 0.0);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let colors = (0..200)
+            .filter_map(|_| palette.get_color_at(&Point(50.0, 50.0), &mut rng))
+            .collect::<Vec<Color>>();
+        assert!(colors.contains(&Color::Hex("#f00")) && colors.contains(&Color::Hex("#00f")));
+    }
+
+    #[test]
+    fn noise_regions() {
+        let regions = Regions::Noise {
+            seed: 4,
+            scale: 0.01,
+        };
+        let palette = RegionalPalette::new(regions.clone(), palettes());
+        let same = RegionalPalette::new(regions, palettes());
+
+        let points = (0..400)
+            .map(|i| Point((i % 20) as f64 * 37.0, (i / 20) as f64 * 37.0))
+            .collect::<Vec<Point>>();
+        let regions = points
+            .iter()
+            .map(|point| palette.region_at(point))
+            .collect::<Vec<Option<usize>>>();
+
+        assert!(regions.contains(&Some(0)) && regions.contains(&Some(1)));
+        assert_eq!(
+            regions,
+            points
+                .iter()
+                .map(|point| same.region_at(point))
+                .collect::<Vec<Option<usize>>>()
+        );
+    }
+
+    #[test]
+    fn without_palettes() {
+        let palette = RegionalPalette::new(Regions::Voronoi(vec![Point(0.0, 0.0)]), vec![]);
+
+        assert_eq!(palette.region_at(&Point(0.0, 0.0)), None);
+        assert_eq!(
+            palette.get_color_at(&Point(0.0, 0.0), &mut rand::thread_rng()),
+            None
+        );
+        assert_eq!(palette.get_random_color(&mut rand::thread_rng()), None);
     }
 }
@@ -0,0 +1,226 @@
+use super::{color::Color, palettes::Palettes, Palette};
+
+/// Builds the background and palette of a registered palette.
+type Build = Box<dyn Fn() -> (Color, Box<dyn Palette>) + Send + Sync>;
+
+/// A palette in a [`PaletteRegistry`].
+pub struct PaletteEntry {
+    name: String,
+    description: String,
+    build: Build,
+}
+
+impl std::fmt::Debug for PaletteEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PaletteEntry")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+impl PaletteEntry {
+    /// The name the palette is looked up by.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A short description of the palette.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The background and the palette, in the same shape as the predefined [`Palettes`].
+    pub fn build(&self) -> (Color, Box<dyn Palette>) {
+        (self.build)()
+    }
+}
+
+/**
+Palettes that can be looked up by name at runtime, e.g. from a command line flag or a
+config file. The default registry has all the predefined [`Palettes`], and custom
+palettes can be added with [`PaletteRegistry::register`].
+
+Names are matched ignoring case, and `-`, `_` and spaces are treated the same, so
+`"orange autumn"`, `"Orange-Autumn"` and `"orange_autumn"` are the same palette.
+
+Example
+
+```
+use art::palette::{
+    color::Color, registry::PaletteRegistry, simple_palette::SimplePalette,
+};
+
+let mut registry = PaletteRegistry::default();
+registry.register("mono", "Black and grey on white", || {
+    let palette = SimplePalette::new([Color::Hex("#000"), Color::Hex("#888")]);
+    (Color::Hex("#fff"), Box::new(palette))
+});
+
+for entry in registry.entries() {
+    println!("{}: {}", entry.name(), entry.description());
+}
+
+let (background, palette) = registry.get("Orange-Autumn").unwrap();
+```
+*/
+#[derive(Debug)]
+pub struct PaletteRegistry {
+    entries: Vec<PaletteEntry>,
+}
+
+impl Default for PaletteRegistry {
+    /// A registry with all the predefined [`Palettes`].
+    fn default() -> Self {
+        let mut registry = PaletteRegistry::empty();
+
+        for (name, description, build) in Palettes::all() {
+            registry.register(name, description, build);
+        }
+
+        registry
+    }
+}
+
+impl PaletteRegistry {
+    /// A registry with all the predefined [`Palettes`], the same as
+    /// [`PaletteRegistry::default`].
+    pub fn new() -> PaletteRegistry {
+        PaletteRegistry::default()
+    }
+
+    /// A registry without any palettes.
+    pub fn empty() -> PaletteRegistry {
+        PaletteRegistry { entries: vec![] }
+    }
+
+    /// Add a palette to the registry. A palette that is already registered under the same
+    /// name is replaced, keeping its place in the list.
+    pub fn register<F>(&mut self, name: &str, description: &str, build: F)
+    where
+        F: Fn() -> (Color, Box<dyn Palette>) + Send + Sync + 'static,
+    {
+        let entry = PaletteEntry {
+            name: name.to_string(),
+            description: description.to_string(),
+            build: Box::new(build),
+        };
+
+        match self.position(name) {
+            Some(i) => self.entries[i] = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// Remove a palette from the registry, returns true if it was registered.
+    pub fn unregister(&mut self, name: &str) -> bool {
+        match self.position(name) {
+            Some(i) => {
+                self.entries.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The background and palette registered as `name`.
+    pub fn get(&self, name: &str) -> Option<(Color, Box<dyn Palette>)> {
+        self.entry(name).map(PaletteEntry::build)
+    }
+
+    /// The entry registered as `name`.
+    pub fn entry(&self, name: &str) -> Option<&PaletteEntry> {
+        self.position(name).map(|i| &self.entries[i])
+    }
+
+    /// The names of all registered palettes, in the order they were registered.
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter().map(PaletteEntry::name).collect()
+    }
+
+    /// All registered palettes, in the order they were registered.
+    pub fn entries(&self) -> &[PaletteEntry] {
+        &self.entries
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        let name = normalize(name);
+        self.entries
+            .iter()
+            .position(|entry| normalize(&entry.name) == name)
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| match c {
+            '-' | ' ' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::palette::{color::Color, palettes::Palettes, simple_palette::SimplePalette};
+
+    use super::PaletteRegistry;
+
+    #[test]
+    fn predefined_palettes() {
+        let registry = PaletteRegistry::default();
+
+        assert_eq!(
+            registry.names(),
+            Palettes::all()
+                .iter()
+                .map(|(name, _, _)| *name)
+                .collect::<Vec<&str>>()
+        );
+        assert_eq!(PaletteRegistry::new().names(), registry.names());
+        assert!(PaletteRegistry::empty().names().is_empty());
+        assert!(registry.names().contains(&"wild"));
+
+        let (background, _) = registry.get("Spring Break").unwrap();
+        assert_eq!(background, Color::Hex("#F9F9F9"));
+        assert!(registry.get("autumn").is_none());
+        assert!(registry
+            .entries()
+            .iter()
+            .all(|entry| !entry.description().is_empty()));
+    }
+
+    #[test]
+    fn register_custom_palettes() {
+        let mut registry = PaletteRegistry::empty();
+        let colors = [Color::Hex("#000"), Color::Hex("#888")];
+
+        registry.register("mono", "Greys", move || {
+            (Color::Hex("#fff"), Box::new(SimplePalette::new(colors)))
+        });
+        registry.register("other", "Other", || {
+            (Color::Hex("#000"), Box::new(SimplePalette::new([])))
+        });
+        registry.register("MONO", "Black on white", || {
+            (
+                Color::Hex("#fff"),
+                Box::new(SimplePalette::new([Color::Hex("#000")])),
+            )
+        });
+
+        assert_eq!(registry.names(), vec!["MONO", "other"]);
+        assert_eq!(
+            registry.entry("mono").unwrap().description(),
+            "Black on white"
+        );
+        assert_eq!(
+            registry.get("mono").unwrap().1.colors(),
+            vec![Color::Hex("#000")]
+        );
+
+        assert!(registry.unregister("other"));
+        assert!(!registry.unregister("other"));
+        assert_eq!(registry.names(), vec!["MONO"]);
+    }
+}
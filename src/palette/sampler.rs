@@ -0,0 +1,219 @@
+use rand::{distributions::WeightedIndex, prelude::Distribution, seq::SliceRandom, RngCore};
+
+use super::{color::Color, Palette};
+
+/// How a [`Sampler`] picks the next color of a palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SamplingStrategy {
+    /// Every pick is independent and biased by the weights, same as
+    /// [`Palette::get_random_color`].
+    #[default]
+    Random,
+
+    /// Every color is put in a bag once and drawn without putting it back, so every color
+    /// appears before any color repeats. The bag is refilled and shuffled once it is empty.
+    /// Weights only decide which colors take part, colors with weight 0 are left out.
+    ShuffleBag,
+
+    /// The colors in the order of the palette, starting over after the last color. Weights
+    /// are ignored.
+    RoundRobin,
+
+    /// Picks biased by the weights, but never the same color twice in a row unless the
+    /// palette has only one color.
+    NoRepeat,
+}
+
+/**
+Picks colors from a palette with a [`SamplingStrategy`], keeping track of the colors that
+were picked before.
+
+Example
+
+```
+use art::palette::{
+    palettes::Palettes,
+    sampler::{Sampler, SamplingStrategy},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+let (_, palette) = Palettes::orange_autumn();
+let mut rng = ChaCha8Rng::seed_from_u64(1);
+let mut sampler = Sampler::new(palette.as_ref(), SamplingStrategy::ShuffleBag);
+
+let color = sampler.next_color(&mut rng);
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sampler {
+    colors: Vec<(Color, usize)>,
+    strategy: SamplingStrategy,
+    bag: Vec<Color>,
+    index: usize,
+    previous: Option<Color>,
+}
+
+impl Sampler {
+    /// Create a sampler for the colors and weights `palette` has right now.
+    pub fn new(palette: &dyn Palette, strategy: SamplingStrategy) -> Sampler {
+        Sampler {
+            colors: palette.weighted_colors(),
+            strategy,
+            bag: vec![],
+            index: 0,
+            previous: None,
+        }
+    }
+
+    /// The next color, or `None` if the palette has no colors to pick from.
+    pub fn next_color(&mut self, rng: &mut dyn RngCore) -> Option<Color> {
+        let color = match self.strategy {
+            SamplingStrategy::Random => self.pick(rng, None),
+            SamplingStrategy::ShuffleBag => {
+                if self.bag.is_empty() {
+                    self.bag = self
+                        .colors
+                        .iter()
+                        .filter(|(_, weight)| *weight > 0)
+                        .map(|(color, _)| *color)
+                        .collect();
+                    self.bag.shuffle(rng);
+                }
+
+                self.bag.pop()
+            }
+            SamplingStrategy::RoundRobin => {
+                let color = self.colors.get(self.index).map(|(color, _)| *color);
+                self.index = (self.index + 1) % self.colors.len().max(1);
+                color
+            }
+            SamplingStrategy::NoRepeat => self
+                .pick(rng, self.previous)
+                .or_else(|| self.pick(rng, None)),
+        };
+
+        self.previous = color;
+        color
+    }
+
+    /// Forget the picked colors, as if the sampler was just created.
+    pub fn reset(&mut self) {
+        self.bag.clear();
+        self.index = 0;
+        self.previous = None;
+    }
+
+    /// A weighted pick, leaving out `exclude`.
+    fn pick(&self, rng: &mut dyn RngCore, exclude: Option<Color>) -> Option<Color> {
+        let weights = self.colors.iter().map(|(color, weight)| match exclude {
+            Some(excluded) if excluded == *color => 0,
+            _ => *weight,
+        });
+
+        let dist = WeightedIndex::new(weights).ok()?;
+        Some(self.colors[dist.sample(rng)].0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::palette::{
+        color::Color, simple_palette::SimplePalette, weighted_palette::WeightedPalette,
+    };
+
+    use super::{Sampler, SamplingStrategy};
+
+    fn palette() -> WeightedPalette<3> {
+        WeightedPalette::new([
+            (Color::Hex("#f00"), 1),
+            (Color::Hex("#0f0"), 2),
+            (Color::Hex("#00f"), 1),
+        ])
+    }
+
+    fn take(sampler: &mut Sampler, count: usize, seed: u64) -> Vec<Color> {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        (0..count)
+            .filter_map(|_| sampler.next_color(&mut rng))
+            .collect()
+    }
+
+    #[test]
+    fn shuffle_bag() {
+        let colors = take(
+            &mut Sampler::new(&palette(), SamplingStrategy::ShuffleBag),
+            9,
+            1,
+        );
+
+        for bag in colors.chunks(3) {
+            let count = |color| bag.iter().filter(|c| **c == Color::Hex(color)).count();
+            assert_eq!((count("#f00"), count("#0f0"), count("#00f")), (1, 1, 1));
+        }
+
+        // Large weights, such as pixel counts, do not make colors repeat within a round.
+        let counts = WeightedPalette::new([
+            (Color::Hex("#f00"), 40_000),
+            (Color::Hex("#0f0"), 3),
+            (Color::Hex("#00f"), 0),
+        ]);
+        let mut sampler = Sampler::new(&counts, SamplingStrategy::ShuffleBag);
+        for round in take(&mut sampler, 6, 2).chunks(2) {
+            assert!(round.contains(&Color::Hex("#f00")) && round.contains(&Color::Hex("#0f0")));
+        }
+    }
+
+    #[test]
+    fn round_robin() {
+        let mut sampler = Sampler::new(&palette(), SamplingStrategy::RoundRobin);
+
+        assert_eq!(
+            take(&mut sampler, 4, 1),
+            vec![
+                Color::Hex("#f00"),
+                Color::Hex("#0f0"),
+                Color::Hex("#00f"),
+                Color::Hex("#f00")
+            ]
+        );
+
+        sampler.reset();
+        assert_eq!(take(&mut sampler, 1, 1), vec![Color::Hex("#f00")]);
+    }
+
+    #[test]
+    fn no_repeat() {
+        let colors = take(
+            &mut Sampler::new(&palette(), SamplingStrategy::NoRepeat),
+            100,
+            1,
+        );
+        assert!(colors.windows(2).all(|pair| pair[0] != pair[1]));
+
+        let single = SimplePalette::new([Color::Hex("#fff")]);
+        assert_eq!(
+            take(&mut Sampler::new(&single, SamplingStrategy::NoRepeat), 2, 1),
+            vec![Color::Hex("#fff"), Color::Hex("#fff")]
+        );
+    }
+
+    #[test]
+    fn seeded_and_empty() {
+        let mut sampler = Sampler::new(&palette(), SamplingStrategy::Random);
+        assert_eq!(take(&mut sampler, 20, 5), take(&mut sampler, 20, 5));
+
+        for strategy in [
+            SamplingStrategy::Random,
+            SamplingStrategy::ShuffleBag,
+            SamplingStrategy::RoundRobin,
+            SamplingStrategy::NoRepeat,
+        ] {
+            let empty = SimplePalette::new([]);
+            assert_eq!(take(&mut Sampler::new(&empty, strategy), 3, 1), vec![]);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use rand::Rng;
+use rand::{Rng, RngCore};
 
 use super::{color::Color, Palette};
 /// A set of colors where a color can be chosen randomly
@@ -14,14 +14,14 @@ impl<const N: usize> SimplePalette<N> {
      Example
 
      ```
-     use generative_art::palette::{Palette, color::Color, simple_palette::SimplePalette};
+     use art::palette::{Palette, color::Color, simple_palette::SimplePalette};
      let palette = SimplePalette::new([
      Color::Hex("#f00"),
      Color::Hex("#0f0"),
      Color::Hex("#00f")
      ]);
 
-     if let Some(random_color) = palette.get_random_color() {
+     if let Some(random_color) = palette.get_random_color(&mut rand::thread_rng()) {
         // do something with random_color
      }
      ```
@@ -33,11 +33,135 @@ impl<const N: usize> SimplePalette<N> {
 }
 
 impl<const N: usize> Palette for SimplePalette<N> {
-    fn get_random_color(&self) -> Option<Color> {
-        let mut rng = rand::thread_rng();
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color> {
         match self.colors.len() {
             0 => None,
-            i => Some(self.colors[rng.gen_range(0..i - 1)]),
+            i => Some(self.colors[rng.gen_range(0..i)]),
         }
     }
+
+    fn weighted_colors(&self) -> Vec<(Color, usize)> {
+        self.colors.iter().map(|color| (*color, 1)).collect()
+    }
+}
+
+/// A set of colors like [`SimplePalette`], but sized at runtime so colors can be added
+/// and removed, e.g. when they are loaded from a file or generated.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DynamicSimplePalette {
+    colors: Vec<Color>,
+}
+
+impl DynamicSimplePalette {
+    /**
+     Create a new color palette
+
+     Example
+
+     ```
+     use art::palette::{Palette, color::Color, simple_palette::DynamicSimplePalette};
+     let mut palette = DynamicSimplePalette::new(vec![Color::Hex("#f00"), Color::Hex("#0f0")]);
+     palette.add(Color::Hex("#00f"));
+     palette.remove(&Color::Hex("#f00"));
+
+     assert_eq!(palette.len(), 2);
+     ```
+
+    */
+    pub fn new(colors: Vec<Color>) -> Self {
+        DynamicSimplePalette { colors }
+    }
+
+    /// Add a color to the palette.
+    pub fn add(&mut self, color: Color) {
+        self.colors.push(color);
+    }
+
+    /// Remove every occurrence of `color`, returns true if the color was in the palette.
+    pub fn remove(&mut self, color: &Color) -> bool {
+        let len = self.colors.len();
+        self.colors.retain(|c| c != color);
+        self.colors.len() != len
+    }
+
+    /// The number of colors in the palette.
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// True if the palette has no colors.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+}
+
+impl<const N: usize> From<SimplePalette<N>> for DynamicSimplePalette {
+    fn from(palette: SimplePalette<N>) -> Self {
+        DynamicSimplePalette::new(palette.colors.to_vec())
+    }
+}
+
+impl FromIterator<Color> for DynamicSimplePalette {
+    fn from_iter<T: IntoIterator<Item = Color>>(iter: T) -> Self {
+        DynamicSimplePalette::new(iter.into_iter().collect())
+    }
+}
+
+impl Palette for DynamicSimplePalette {
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color> {
+        match self.colors.len() {
+            0 => None,
+            i => Some(self.colors[rng.gen_range(0..i)]),
+        }
+    }
+
+    fn weighted_colors(&self) -> Vec<(Color, usize)> {
+        self.colors.iter().map(|color| (*color, 1)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::palette::{color::Color, Palette};
+
+    use super::{DynamicSimplePalette, SimplePalette};
+
+    #[test]
+    fn samples_every_color() {
+        let palette = SimplePalette::new([Color::Hex("#f00"), Color::Hex("#0f0")]);
+        let colors = (0..200)
+            .filter_map(|_| palette.get_random_color(&mut rand::thread_rng()))
+            .collect::<Vec<Color>>();
+
+        assert!(colors.contains(&Color::Hex("#f00")));
+        assert!(colors.contains(&Color::Hex("#0f0")));
+        assert_eq!(
+            SimplePalette::new([Color::Hex("#00f")]).get_random_color(&mut rand::thread_rng()),
+            Some(Color::Hex("#00f"))
+        );
+        assert_eq!(
+            SimplePalette::new([]).get_random_color(&mut rand::thread_rng()),
+            None
+        );
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let mut palette: DynamicSimplePalette =
+            SimplePalette::new([Color::Hex("#f00"), Color::Hex("#0f0")]).into();
+
+        palette.add(Color::Hex("#00f"));
+        assert!(palette.remove(&Color::Hex("#f00")));
+        assert!(!palette.remove(&Color::Hex("#f00")));
+
+        assert_eq!(
+            palette.colors(),
+            vec![Color::Hex("#0f0"), Color::Hex("#00f")]
+        );
+
+        palette.remove(&Color::Hex("#0f0"));
+        palette.remove(&Color::Hex("#00f"));
+        assert!(palette.is_empty());
+        assert_eq!(palette.get_random_color(&mut rand::thread_rng()), None);
+    }
 }
@@ -0,0 +1,363 @@
+use std::path::Path;
+
+use image::{ImageResult, RgbImage};
+
+use crate::{
+    raster::canvas::Canvas,
+    shapes::{point::Point, rectangle::Rectangle, shape::Shape, transform::Transform},
+    svg::document::Document,
+};
+
+use super::{color::Color, Palette};
+
+/// Space around the swatches of a row, and between a swatch and its label.
+const PADDING: f64 = 24.0;
+
+/// Space between two swatches.
+const GAP: f64 = 8.0;
+
+/// Size of one pixel of the label font, the glyphs are 3 by 5 of these.
+const LABEL_SCALE: f64 = 3.0;
+
+/**
+A sheet with one row per palette, to compare palettes side by side. Every row shows the
+background as a band, with the colors of the palette on top of it as swatches that are
+as wide as their weight, labeled with their hex code.
+
+Example
+
+```no_run
+use art::palette::{palettes::Palettes, swatch::SwatchSheet};
+
+let (background, palette) = Palettes::orange_autumn();
+let sheet = SwatchSheet::new().add_palette(background, palette.as_ref());
+
+sheet.to_document("orange-autumn").save();
+sheet.save_png("orange-autumn.png").unwrap();
+```
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwatchSheet {
+    width: f64,
+    row_height: f64,
+    rows: Vec<(Color, Vec<(Color, usize)>)>,
+}
+
+impl Default for SwatchSheet {
+    fn default() -> Self {
+        SwatchSheet {
+            width: 1200.0,
+            row_height: 200.0,
+            rows: vec![],
+        }
+    }
+}
+
+impl SwatchSheet {
+    /// An empty sheet, 1200 wide with rows that are 200 high.
+    pub fn new() -> SwatchSheet {
+        SwatchSheet::default()
+    }
+
+    /// The width of the sheet.
+    pub fn width(mut self, width: f64) -> SwatchSheet {
+        self.width = width;
+        self
+    }
+
+    /// The height of every row. Rows need to be at least 75 high to fit the swatches
+    /// between the padding and the labels.
+    pub fn row_height(mut self, height: f64) -> SwatchSheet {
+        self.row_height = height;
+        self
+    }
+
+    /// Add a row with the colors of `palette` on `background`. Colors that are not valid,
+    /// such as a [`Color::Hex`] that does not hold a hex color, get no swatch.
+    pub fn add_palette(mut self, background: Color, palette: &dyn Palette) -> SwatchSheet {
+        self.rows.push((background, palette.weighted_colors()));
+        self
+    }
+
+    /// The height of the whole sheet.
+    pub fn height(&self) -> f64 {
+        self.rows.len() as f64 * self.row_height
+    }
+
+    /// The background bands, swatches and labels of the sheet.
+    pub fn shapes(&self) -> Vec<Box<dyn Shape>> {
+        let mut shapes: Vec<Box<dyn Shape>> = vec![];
+        // Rows that are too low for the padding and labels only show the background.
+        let swatch_height = (self.row_height - PADDING * 2.5 - LABEL_SCALE * 5.0).max(0.0);
+
+        for (row, (background, colors)) in self.rows.iter().enumerate() {
+            let y = row as f64 * self.row_height;
+            shapes.push(Box::new(
+                Rectangle::new(Point(0.0, y), self.width, self.row_height).set_color(*background),
+            ));
+
+            // Colors that can not be shown, such as invalid hex colors, are left out.
+            let colors = colors
+                .iter()
+                .filter(|(_, weight)| *weight > 0)
+                .filter_map(|(color, weight)| Some((*color, *weight, color.try_to_hex().ok()?)))
+                .collect::<Vec<(Color, usize, String)>>();
+
+            let total = colors.iter().map(|(_, weight, _)| weight).sum::<usize>();
+            if total == 0 {
+                continue;
+            }
+
+            let label_color = [Color::Hex("#000000"), Color::Hex("#ffffff")]
+                .into_iter()
+                .max_by(|a, b| {
+                    a.contrast_ratio(background)
+                        .total_cmp(&b.contrast_ratio(background))
+                })
+                .unwrap_or(Color::Hex("#000000"));
+
+            let gaps = GAP * colors.len() as f64;
+            let unit = (self.width - PADDING * 2.0 - gaps + GAP) / total as f64;
+            let mut x = PADDING;
+
+            for (color, weight, hex) in colors {
+                let width = unit * weight as f64;
+                shapes.push(Box::new(
+                    Rectangle::new(Point(x, y + PADDING), width, swatch_height).set_color(color),
+                ));
+
+                let label = Label {
+                    position: Point(x, y + PADDING * 1.5 + swatch_height),
+                    text: hex,
+                    color: label_color,
+                };
+                if label.width() <= width {
+                    shapes.push(Box::new(label));
+                }
+
+                x += width + GAP;
+            }
+        }
+
+        shapes
+    }
+
+    /// The sheet as an SVG document.
+    pub fn to_document(&self, name: &'static str) -> Document<'static> {
+        let mut document = Document::new(
+            name,
+            Rectangle::new(Point(0.0, 0.0), self.width, self.height()),
+        );
+
+        for shape in self.shapes() {
+            document.add_shape(shape);
+        }
+
+        document
+    }
+
+    /// The sheet drawn on a canvas, with one pixel per unit.
+    pub fn to_canvas(&self) -> Canvas {
+        let mut canvas = Canvas::new(self.width.ceil() as u32, self.height().ceil() as u32);
+
+        for shape in self.shapes() {
+            shape.render_blended(&mut canvas);
+        }
+
+        canvas
+    }
+
+    /// Save the sheet as an image, the format is decided by the file extension.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> ImageResult<()> {
+        self.to_canvas().save(path)
+    }
+}
+
+/// Hex code text, drawn with a tiny bitmap font so it looks the same in rasters as in SVG.
+#[derive(Debug, Clone)]
+struct Label {
+    position: Point,
+    text: String,
+    color: Color,
+}
+
+impl Label {
+    fn width(&self) -> f64 {
+        (self.text.chars().count() * 4) as f64 * LABEL_SCALE - LABEL_SCALE
+    }
+
+    /// One square for every lit pixel of the glyphs.
+    fn pixels(&self) -> Vec<Rectangle> {
+        let mut pixels = vec![];
+
+        for (i, c) in self.text.chars().enumerate() {
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for column in 0..3 {
+                    if bits & (0b100 >> column) != 0 {
+                        let x = self.position.0 + (i * 4 + column) as f64 * LABEL_SCALE;
+                        let y = self.position.1 + row as f64 * LABEL_SCALE;
+                        pixels.push(
+                            Rectangle::new(Point(x, y), LABEL_SCALE, LABEL_SCALE)
+                                .set_color(self.color),
+                        );
+                    }
+                }
+            }
+        }
+
+        pixels
+    }
+}
+
+impl Shape for Label {
+    fn as_svg(&self) -> String {
+        format!(
+            "<text x=\"{:.2}\" y=\"{:.2}\" font-family=\"monospace\" font-size=\"{:.2}\" textLength=\"{:.2}\" fill=\"{}\">{}</text>",
+            self.position.0,
+            self.position.1 + LABEL_SCALE * 5.0,
+            LABEL_SCALE * 7.0,
+            self.width(),
+            self.color,
+            self.text
+        )
+    }
+
+    fn render(&self, image: &mut RgbImage) {
+        for pixel in self.pixels() {
+            pixel.render(image);
+        }
+    }
+
+    fn render_blended(&self, canvas: &mut Canvas) {
+        for pixel in self.pixels() {
+            pixel.render_blended(canvas);
+        }
+    }
+
+    fn center(&self) -> Point {
+        Point(
+            self.position.0 + self.width() / 2.0,
+            self.position.1 + LABEL_SCALE * 2.5,
+        )
+    }
+
+    fn bounding_box(&self) -> Option<Rectangle> {
+        Some(Rectangle::new(
+            self.position,
+            self.width(),
+            LABEL_SCALE * 5.0,
+        ))
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        self.bounding_box()
+            .is_some_and(|bounding_box| bounding_box.contains(point))
+    }
+
+    /// Only moves the label, the text is never rotated or scaled.
+    fn transform(&self, transform: &Transform) -> Label {
+        Label {
+            position: transform.apply(&self.position),
+            ..self.clone()
+        }
+    }
+}
+
+/// Rows of a 3 by 5 glyph, the highest of the 3 bits is the leftmost pixel.
+fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_lowercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'a' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'b' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'c' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'd' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'e' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'f' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        _ => [0; 5],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::palette::{color::Color, weighted_palette::WeightedPalette};
+
+    use super::SwatchSheet;
+
+    fn sheet() -> SwatchSheet {
+        let palette = WeightedPalette::new([
+            (Color::Hex("#ff0000"), 3),
+            (Color::Hex("#0000ff"), 1),
+            (Color::Hex("#00ff00"), 0),
+        ]);
+
+        SwatchSheet::new()
+            .width(400.0)
+            .add_palette(Color::Hex("#ffffff"), &palette)
+            .add_palette(Color::Hex("#000000"), &palette)
+    }
+
+    #[test]
+    fn svg_rows() {
+        let svg = sheet().to_document("swatches").generate();
+
+        assert_eq!(sheet().height(), 400.0);
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert_eq!(svg.matches("<text").count(), 4);
+        assert!(svg.contains(">#ff0000</text>"));
+        assert!(svg.contains("fill=\"#ffffff\">#ff0000"));
+        assert!(svg.contains("fill=\"#000000\">#ff0000"));
+
+        // The blue swatches get too narrow for their labels.
+        let svg = sheet().width(300.0).to_document("swatches").generate();
+        assert_eq!(svg.matches("<text").count(), 2);
+        assert!(!svg.contains(">#0000ff</text>"));
+    }
+
+    #[test]
+    fn swatches_sized_by_weight() {
+        let canvas = sheet().to_canvas();
+        let pixel = |x: u32, y: u32| canvas.image().get_pixel(x, y).0;
+
+        assert_eq!((canvas.width(), canvas.height()), (400, 400));
+        assert_eq!(pixel(5, 5), [255, 255, 255, 255]);
+        assert_eq!(pixel(30, 100), [255, 0, 0, 255]);
+        assert_eq!(pixel(280, 100), [255, 0, 0, 255]);
+        assert_eq!(pixel(300, 100), [0, 0, 255, 255]);
+        assert_eq!(pixel(30, 300), [255, 0, 0, 255]);
+        assert_eq!(pixel(5, 305), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn invalid_colors() {
+        let palette = WeightedPalette::new([(Color::Hex("#ff0000"), 1), (Color::Hex("#nope"), 1)]);
+        let sheet = SwatchSheet::new().add_palette(Color::Hex("#ffffff"), &palette);
+
+        let svg = sheet.to_document("swatches").generate();
+        assert_eq!(svg.matches("<rect").count(), 2);
+        assert!(!svg.contains("#nope"));
+        assert_eq!(
+            sheet.to_canvas().image().get_pixel(600, 100).0,
+            [255, 0, 0, 255]
+        );
+    }
+
+    #[test]
+    fn low_rows() {
+        let shapes = sheet().row_height(20.0).shapes();
+
+        assert!(shapes
+            .iter()
+            .filter_map(|shape| shape.bounding_box())
+            .all(|bounds| bounds.height >= 0.0));
+    }
+}
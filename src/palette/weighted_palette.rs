@@ -1,5 +1,5 @@
 use super::{color::Color, Palette};
-use rand::{distributions::WeightedIndex, prelude::Distribution};
+use rand::{distributions::WeightedIndex, prelude::Distribution, RngCore};
 
 /// A set of colors where one can be chosen randomly but biased by a given weight
 #[derive(Debug)]
@@ -15,7 +15,7 @@ impl<const N: usize> WeightedPalette<N> {
 
      ```
 
-     use generative_art::{palette::{Palette, color::Color, weighted_palette::WeightedPalette}};
+     use art::{palette::{Palette, color::Color, weighted_palette::WeightedPalette}};
 
      let palette = WeightedPalette::new([
         (Color::Hex("#f00"), 1),
@@ -23,7 +23,7 @@ impl<const N: usize> WeightedPalette<N> {
         (Color::Hex("#00f"), 1)
      ]);
 
-     if let Some(random_color) = palette.get_random_color() {
+     if let Some(random_color) = palette.get_random_color(&mut rand::thread_rng()) {
      // random_color has 5 times as high of a chance to be picked as
      // either red or blue.
      }
@@ -36,8 +36,7 @@ impl<const N: usize> WeightedPalette<N> {
 }
 
 impl<const N: usize> Palette for WeightedPalette<N> {
-    fn get_random_color(&self) -> Option<Color> {
-        let mut rng = rand::thread_rng();
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color> {
         let weights = self
             .colors
             .iter()
@@ -50,11 +49,144 @@ impl<const N: usize> Palette for WeightedPalette<N> {
             return None;
         };
 
-        let i = dist.sample(&mut rng);
+        let i = dist.sample(rng);
 
         match self.colors.len() {
             0 => None,
             _ => Some(self.colors[i].0),
         }
     }
+
+    fn weighted_colors(&self) -> Vec<(Color, usize)> {
+        self.colors.to_vec()
+    }
+}
+
+/// A set of weighted colors like [`WeightedPalette`], but sized at runtime so colors can be
+/// added, removed and reweighted, e.g. when they are loaded from a file or generated.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DynamicWeightedPalette {
+    colors: Vec<(Color, usize)>,
+}
+
+impl DynamicWeightedPalette {
+    /**
+     Create a new weighted color palette
+
+     Example
+
+     ```
+     use art::palette::{Palette, color::Color, weighted_palette::DynamicWeightedPalette};
+
+     let mut palette = DynamicWeightedPalette::new(vec![(Color::Hex("#f00"), 1)]);
+     palette.add(Color::Hex("#0f0"), 5);
+     palette.set_weight(&Color::Hex("#f00"), 2);
+
+     assert_eq!(palette.weighted_colors()[0], (Color::Hex("#f00"), 2));
+     ```
+
+    */
+    pub fn new(colors: Vec<(Color, usize)>) -> Self {
+        DynamicWeightedPalette { colors }
+    }
+
+    /// Add a color with the given weight, if the color is already in the palette the
+    /// weight is added to it.
+    pub fn add(&mut self, color: Color, weight: usize) {
+        match self.colors.iter_mut().find(|(c, _)| *c == color) {
+            Some((_, total)) => *total += weight,
+            None => self.colors.push((color, weight)),
+        }
+    }
+
+    /// Remove `color` from the palette, returns its weight if it was in the palette.
+    pub fn remove(&mut self, color: &Color) -> Option<usize> {
+        let i = self.colors.iter().position(|(c, _)| c == color)?;
+        Some(self.colors.remove(i).1)
+    }
+
+    /// Change the weight of `color`, returns false if the color is not in the palette.
+    pub fn set_weight(&mut self, color: &Color, weight: usize) -> bool {
+        match self.colors.iter_mut().find(|(c, _)| c == color) {
+            Some((_, w)) => {
+                *w = weight;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The number of colors in the palette.
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// True if the palette has no colors.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+}
+
+impl<const N: usize> From<WeightedPalette<N>> for DynamicWeightedPalette {
+    fn from(palette: WeightedPalette<N>) -> Self {
+        DynamicWeightedPalette::new(palette.colors.to_vec())
+    }
+}
+
+impl FromIterator<(Color, usize)> for DynamicWeightedPalette {
+    fn from_iter<T: IntoIterator<Item = (Color, usize)>>(iter: T) -> Self {
+        let mut palette = DynamicWeightedPalette::default();
+        for (color, weight) in iter {
+            palette.add(color, weight);
+        }
+        palette
+    }
+}
+
+impl Palette for DynamicWeightedPalette {
+    fn get_random_color(&self, rng: &mut dyn RngCore) -> Option<Color> {
+        let dist = WeightedIndex::new(self.colors.iter().map(|(_, weight)| *weight)).ok()?;
+        Some(self.colors[dist.sample(rng)].0)
+    }
+
+    fn weighted_colors(&self) -> Vec<(Color, usize)> {
+        self.colors.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::palette::{color::Color, Palette};
+
+    use super::{DynamicWeightedPalette, WeightedPalette};
+
+    #[test]
+    fn add_remove_and_reweight() {
+        let mut palette: DynamicWeightedPalette =
+            WeightedPalette::new([(Color::Hex("#f00"), 1), (Color::Hex("#0f0"), 2)]).into();
+
+        palette.add(Color::Hex("#f00"), 2);
+        palette.add(Color::Hex("#00f"), 1);
+        assert_eq!(palette.remove(&Color::Hex("#0f0")), Some(2));
+        assert_eq!(palette.remove(&Color::Hex("#0f0")), None);
+        assert!(palette.set_weight(&Color::Hex("#00f"), 0));
+        assert!(!palette.set_weight(&Color::Hex("#fff"), 1));
+
+        assert_eq!(
+            palette.weighted_colors(),
+            vec![(Color::Hex("#f00"), 3), (Color::Hex("#00f"), 0)]
+        );
+        assert_eq!(
+            palette.get_random_color(&mut rand::thread_rng()),
+            Some(Color::Hex("#f00"))
+        );
+    }
+
+    #[test]
+    fn empty_palette() {
+        let palette = DynamicWeightedPalette::from_iter([]);
+
+        assert!(palette.is_empty());
+        assert_eq!(palette.get_random_color(&mut rand::thread_rng()), None);
+    }
 }
@@ -0,0 +1,527 @@
+use std::path::Path;
+
+use image::{ImageBuffer, ImageResult, RgbImage, RgbaImage};
+
+use crate::{palette::color::Color, shapes::point::Point};
+
+/**
+A raster image that shapes are composited onto. Unlike drawing straight into an
+[`RgbImage`], every pixel a shape covers is blended with what is already on the canvas
+using the alpha of the shape's color (Porter-Duff "over"), so translucent layers look
+the same as they do in the SVG output.
+
+Example
+
+```
+use art::{
+    palette::color::Color,
+    raster::canvas::Canvas,
+    shapes::{circle::Circle, point::Point, shape::Shape},
+};
+
+let mut canvas = Canvas::new(100, 100);
+canvas.fill(Color::Hex("#fff"));
+
+let mut circle = Circle::new(Point(50.0, 50.0), 20.0);
+circle.set_color(Color::HSLa(37, 80.0, 55.0, 0.1));
+circle.render_blended(&mut canvas);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    image: RgbaImage,
+    blend_mode: BlendMode,
+}
+
+/// How the color of a shape is combined with the colors already on a [`Canvas`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Blend the colors like light, the way SVG renderers do.
+    #[default]
+    Normal,
+
+    /**
+    Mix the colors like paint with [`Color::mix_pigment`], so translucent blue strokes
+    over yellow ones turn green instead of grey. The alpha is composited the same as
+    with [`BlendMode::Normal`].
+
+    Example
+
+    ```
+    use art::{
+        palette::color::Color,
+        raster::canvas::{BlendMode, Canvas},
+    };
+
+    let mut canvas = Canvas::new(100, 100);
+    canvas.set_blend_mode(BlendMode::Pigment);
+    canvas.fill(Color::Hex("#ffff00"));
+    canvas.fill(Color::Rgba(0.0, 0.0, 255.0, 0.5));
+    ```
+    */
+    Pigment,
+}
+
+impl Canvas {
+    /// Create a new, fully transparent canvas.
+    pub fn new(width: u32, height: u32) -> Canvas {
+        Canvas {
+            image: ImageBuffer::new(width, height),
+            blend_mode: BlendMode::default(),
+        }
+    }
+
+    /// How shapes drawn from now on are combined with what is already on the canvas.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    /// The current blend mode.
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    /// Width of the canvas in pixels.
+    pub fn width(&self) -> u32 {
+        self.image.width()
+    }
+
+    /// Height of the canvas in pixels.
+    pub fn height(&self) -> u32 {
+        self.image.height()
+    }
+
+    /// Blend `color` over the entire canvas.
+    pub fn fill(&mut self, color: Color) {
+        let color = color.srgba();
+
+        for pixel in self.image.pixels_mut() {
+            over(pixel, color, self.blend_mode);
+        }
+    }
+
+    /// Blend `color` over a single pixel. Pixels outside of the canvas are ignored.
+    pub fn blend_pixel(&mut self, x: i64, y: i64, color: Color) {
+        if x < 0 || y < 0 || x >= self.width() as i64 || y >= self.height() as i64 {
+            return;
+        }
+
+        let blend_mode = self.blend_mode;
+        over(
+            self.image.get_pixel_mut(x as u32, y as u32),
+            color.srgba(),
+            blend_mode,
+        );
+    }
+
+    /// Blend `color` over every pixel whose center is inside the polygon made up of `points`.
+    /// The polygon is closed automatically and self-intersections are filled using the
+    /// nonzero winding rule, the same as the SVG default.
+    pub fn fill_polygon(&mut self, points: &[Point], color: Color) {
+        if points.is_empty() {
+            return;
+        }
+
+        let mut coverage = Coverage::around(self, points, 0.0);
+        coverage.polygon(points);
+        self.blend_coverage(&coverage, color);
+    }
+
+    /// Blend `color` over every pixel whose center is inside the circle.
+    pub fn fill_circle(&mut self, center: Point, radius: f64, color: Color) {
+        let mut coverage = Coverage::around(self, &[center], radius);
+        coverage.circle(center, radius);
+        self.blend_coverage(&coverage, color);
+    }
+
+    /// Blend a line of the given `width` through `points` over the canvas. Every pixel is
+    /// blended at most once, so translucent lines do not get darker where segments meet.
+    /// Lines that are 1 pixel wide or thinner are drawn as single pixel lines.
+    pub fn stroke_polyline(&mut self, points: &[Point], width: f64, color: Color) {
+        if points.is_empty() {
+            return;
+        }
+
+        let mut coverage = Coverage::around(self, points, width / 2.0 + 1.0);
+        coverage.polyline(points, width);
+        self.blend_coverage(&coverage, color);
+    }
+
+    /// The underlying image.
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+
+    /// Consume the canvas and return the underlying image.
+    pub fn into_image(self) -> RgbaImage {
+        self.image
+    }
+
+    /// Copy of the canvas without the alpha channel, for formats such as JPEG that do not
+    /// support transparency. Fill the canvas with an opaque background first.
+    pub fn to_rgb(&self) -> RgbImage {
+        ImageBuffer::from_fn(self.width(), self.height(), |x, y| {
+            let [r, g, b, _] = self.image.get_pixel(x, y).0;
+            image::Rgb([r, g, b])
+        })
+    }
+
+    /// Save the canvas to disk, the format is decided by the file extension.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> ImageResult<()> {
+        let path = path.as_ref();
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("jpg" | "jpeg") => self.to_rgb().save(path),
+            _ => self.image.save(path),
+        }
+    }
+
+    fn blend_coverage(&mut self, coverage: &Coverage, color: Color) {
+        let color = color.srgba();
+
+        for (x, y) in coverage.pixels() {
+            over(self.image.get_pixel_mut(x, y), color, self.blend_mode);
+        }
+    }
+}
+
+/// Porter-Duff "over" with straight alpha, `color` is sRGB and alpha in `0..=1`.
+fn over(pixel: &mut image::Rgba<u8>, color: (f64, f64, f64, f64), blend_mode: BlendMode) {
+    let (r, g, b, alpha) = color;
+    let alpha = alpha.clamp(0.0, 1.0);
+
+    if alpha == 0.0 {
+        return;
+    }
+
+    let [dr, dg, db, da] = pixel.0.map(|channel| channel as f64 / 255.0);
+    let out_alpha = alpha + da * (1.0 - alpha);
+
+    if blend_mode == BlendMode::Pigment && da > 0.0 {
+        // The share of the new color in the result is its share of the combined alpha.
+        let destination = Color::Rgba(dr * 255.0, dg * 255.0, db * 255.0, 1.0);
+        let source = Color::Rgba(r * 255.0, g * 255.0, b * 255.0, 1.0);
+        let (r, g, b, _) = destination.mix_pigment(&source, alpha / out_alpha).srgba();
+
+        pixel.0 =
+            [r, g, b, out_alpha].map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8);
+        return;
+    }
+    let channel = |source: f64, destination: f64| {
+        let value = (source.clamp(0.0, 1.0) * alpha + destination * da * (1.0 - alpha)) / out_alpha;
+        (value * 255.0).round() as u8
+    };
+
+    pixel.0 = [
+        channel(r, dr),
+        channel(g, dg),
+        channel(b, db),
+        (out_alpha * 255.0).round() as u8,
+    ];
+}
+
+/// The set of pixels covered by a shape, limited to the area of the canvas the shape
+/// can touch. Shapes are rasterized into a coverage first so that each pixel is only
+/// blended once, even if the shape overlaps itself.
+#[derive(Debug)]
+struct Coverage {
+    x: i64,
+    y: i64,
+    width: i64,
+    height: i64,
+    mask: Vec<bool>,
+}
+
+impl Coverage {
+    /// An empty coverage around `points`, grown by `padding`, clipped to the canvas.
+    fn around(canvas: &Canvas, points: &[Point], padding: f64) -> Coverage {
+        let (min_x, min_y, max_x, max_y) = points.iter().fold(
+            (f64::MAX, f64::MAX, f64::MIN, f64::MIN),
+            |(x1, y1, x2, y2), point| {
+                (
+                    x1.min(point.0),
+                    y1.min(point.1),
+                    x2.max(point.0),
+                    y2.max(point.1),
+                )
+            },
+        );
+
+        let x = ((min_x - padding).floor() as i64).max(0);
+        let y = ((min_y - padding).floor() as i64).max(0);
+        let right = ((max_x + padding).ceil() as i64 + 1).min(canvas.width() as i64);
+        let bottom = ((max_y + padding).ceil() as i64 + 1).min(canvas.height() as i64);
+        let width = (right - x).max(0);
+        let height = (bottom - y).max(0);
+
+        Coverage {
+            x,
+            y,
+            width,
+            height,
+            mask: vec![false; (width * height) as usize],
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64) {
+        let (x, y) = (x - self.x, y - self.y);
+
+        if x >= 0 && y >= 0 && x < self.width && y < self.height {
+            self.mask[(y * self.width + x) as usize] = true;
+        }
+    }
+
+    /// Cover the pixels in row `y` whose centers lie in `from..to`.
+    fn span(&mut self, y: i64, from: f64, to: f64) {
+        let start = ((from - 0.5).ceil() as i64).max(self.x);
+        let end = ((to - 0.5).ceil() as i64).min(self.x + self.width);
+
+        for x in start..end {
+            self.set(x, y);
+        }
+    }
+
+    fn polygon(&mut self, points: &[Point]) {
+        if points.len() < 3 {
+            return;
+        }
+
+        for y in self.y..self.y + self.height {
+            let center = y as f64 + 0.5;
+            let mut crossings = points
+                .iter()
+                .zip(points.iter().cycle().skip(1))
+                .filter_map(|(a, b)| {
+                    let direction = if a.1 <= center && b.1 > center {
+                        1
+                    } else if b.1 <= center && a.1 > center {
+                        -1
+                    } else {
+                        return None;
+                    };
+
+                    let x = a.0 + (center - a.1) / (b.1 - a.1) * (b.0 - a.0);
+                    Some((x, direction))
+                })
+                .collect::<Vec<(f64, i32)>>();
+
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for window in crossings.windows(2) {
+                winding += window[0].1;
+                if winding != 0 {
+                    self.span(y, window[0].0, window[1].0);
+                }
+            }
+        }
+    }
+
+    fn circle(&mut self, center: Point, radius: f64) {
+        for y in self.y..self.y + self.height {
+            let dy = y as f64 + 0.5 - center.1;
+
+            if dy.abs() <= radius {
+                let half = (radius.powi(2) - dy.powi(2)).sqrt();
+                self.span(y, center.0 - half, center.0 + half);
+            }
+        }
+    }
+
+    fn polyline(&mut self, points: &[Point], width: f64) {
+        if width <= 1.0 {
+            for pair in points.windows(2) {
+                self.line(pair[0], pair[1]);
+            }
+
+            if let [point] = points {
+                self.set(point.0.floor() as i64, point.1.floor() as i64);
+            }
+
+            return;
+        }
+
+        let half = width / 2.0;
+
+        for pair in points.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let length = a.distance_to(&b);
+
+            if length == 0.0 {
+                continue;
+            }
+
+            let (nx, ny) = (-(b.1 - a.1) / length * half, (b.0 - a.0) / length * half);
+
+            self.polygon(&[
+                a.offset(nx, ny),
+                b.offset(nx, ny),
+                b.offset(-nx, -ny),
+                a.offset(-nx, -ny),
+            ]);
+        }
+
+        for joint in points.iter().skip(1).take(points.len().saturating_sub(2)) {
+            self.circle(*joint, half);
+        }
+    }
+
+    /// Bresenham line between the pixels containing `from` and `to`.
+    fn line(&mut self, from: Point, to: Point) {
+        let (mut x, mut y) = (from.0.floor() as i64, from.1.floor() as i64);
+        let (x1, y1) = (to.0.floor() as i64, to.1.floor() as i64);
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut error = dx + dy;
+
+        loop {
+            self.set(x, y);
+
+            if x == x1 && y == y1 {
+                break;
+            }
+
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    fn pixels(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.mask
+            .iter()
+            .enumerate()
+            .filter(|(_, covered)| **covered)
+            .map(|(i, _)| {
+                let i = i as i64;
+                (
+                    (self.x + i % self.width) as u32,
+                    (self.y + i / self.width) as u32,
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{palette::color::Color, shapes::point::Point};
+
+    use super::{BlendMode, Canvas};
+
+    #[test]
+    fn blend_over_opaque() {
+        let mut canvas = Canvas::new(1, 1);
+        canvas.fill(Color::Hex("#ffffff"));
+        canvas.blend_pixel(0, 0, Color::Rgba(0.0, 0.0, 0.0, 0.5));
+
+        assert_eq!(canvas.image().get_pixel(0, 0).0, [128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn blend_over_transparent() {
+        let mut canvas = Canvas::new(1, 1);
+        canvas.blend_pixel(0, 0, Color::Rgba(255.0, 0.0, 0.0, 0.5));
+        canvas.blend_pixel(0, 0, Color::Rgba(255.0, 0.0, 0.0, 0.5));
+
+        assert_eq!(canvas.image().get_pixel(0, 0).0, [255, 0, 0, 192]);
+    }
+
+    #[test]
+    fn out_of_bounds_is_ignored() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.blend_pixel(-1, 5, Color::Hex("#000"));
+        canvas.fill_circle(Point(-100.0, -100.0), 10.0, Color::Hex("#000"));
+
+        assert!(canvas.image().pixels().all(|pixel| pixel.0 == [0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn fill_polygon_covers_pixel_centers() {
+        let mut canvas = Canvas::new(4, 4);
+        canvas.fill_polygon(
+            &[
+                Point(1.0, 1.0),
+                Point(3.0, 1.0),
+                Point(3.0, 3.0),
+                Point(1.0, 3.0),
+            ],
+            Color::Hex("#000"),
+        );
+
+        let covered = canvas
+            .image()
+            .enumerate_pixels()
+            .filter(|(_, _, pixel)| pixel.0[3] == 255)
+            .map(|(x, y, _)| (x, y))
+            .collect::<Vec<(u32, u32)>>();
+
+        assert_eq!(covered, vec![(1, 1), (2, 1), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn fill_empty_polygon() {
+        let mut canvas = Canvas::new(4, 4);
+        canvas.fill_polygon(&[], Color::Hex("#000"));
+
+        assert!(canvas.image().pixels().all(|pixel| pixel.0 == [0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn stroke_empty_polyline() {
+        let mut canvas = Canvas::new(4, 4);
+        canvas.stroke_polyline(&[], 1.0, Color::Hex("#000"));
+        canvas.stroke_polyline(&[], 3.0, Color::Hex("#000"));
+
+        assert!(canvas.image().pixels().all(|pixel| pixel.0 == [0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn stroke_blends_each_pixel_once() {
+        let mut canvas = Canvas::new(10, 10);
+        canvas.fill(Color::Hex("#ffffff"));
+        canvas.stroke_polyline(
+            &[Point(1.0, 1.0), Point(8.0, 1.0), Point(8.0, 8.0)],
+            3.0,
+            Color::Rgba(0.0, 0.0, 0.0, 0.5),
+        );
+
+        assert!(canvas
+            .image()
+            .pixels()
+            .all(|pixel| pixel.0 == [255, 255, 255, 255] || pixel.0 == [128, 128, 128, 255]));
+        assert_eq!(canvas.image().get_pixel(8, 1).0, [128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn pigment_blend_mode() {
+        let mut canvas = Canvas::new(3, 1);
+        canvas.set_blend_mode(BlendMode::Pigment);
+        assert_eq!(canvas.blend_mode(), BlendMode::Pigment);
+
+        canvas.blend_pixel(0, 0, Color::Rgba(0.0, 0.0, 255.0, 0.5));
+        assert_eq!(canvas.image().get_pixel(0, 0).0, [0, 0, 255, 128]);
+
+        canvas.fill(Color::Hex("#ffff00"));
+        canvas.stroke_polyline(
+            &[Point(1.0, 0.0), Point(2.0, 0.0)],
+            1.0,
+            Color::Rgba(0.0, 0.0, 255.0, 0.5),
+        );
+
+        let [r, g, b, a] = canvas.image().get_pixel(1, 0).0;
+        assert!(g > r && g > b && a == 255, "{:?}", [r, g, b, a]);
+
+        let mut normal = Canvas::new(1, 1);
+        normal.fill(Color::Hex("#ffff00"));
+        normal.blend_pixel(0, 0, Color::Rgba(0.0, 0.0, 255.0, 0.5));
+        assert_eq!(normal.image().get_pixel(0, 0).0, [128, 128, 128, 255]);
+    }
+}
@@ -0,0 +1,482 @@
+use image::RgbImage;
+
+use crate::raster::canvas::Canvas;
+
+use super::{
+    path::Path, path_style::PathStyle, point::Point, polygon::Polygon, rectangle::Rectangle,
+    shape::Shape, transform::Transform,
+};
+
+/// How the ends of an [`Arc`] are connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArcKind {
+    /// Only the curve. When filled, the fill goes up to the straight line between the ends,
+    /// the same as SVG.
+    #[default]
+    Open,
+
+    /// The curve and a straight line between its ends.
+    Chord,
+
+    /// The curve and straight lines from both ends to the center, a slice of pie.
+    Sector,
+}
+
+/**
+A part of the outline of a circle, going from `start_angle` to `end_angle`. Angles are in
+degrees, measured clockwise from the positive x axis since the y axis points down. The
+arc goes counterclockwise when `end_angle` is smaller than `start_angle`.
+
+Like [`Path`], the arc is only filled if it has a fill color.
+
+Example
+
+```
+use art::{
+    palette::color::Color,
+    shapes::{arc::Arc, path_style::PathStyle, point::Point, shape::Shape},
+};
+
+let mut slice = Arc::sector(Point(50.0, 50.0), 40.0, -90.0, 0.0);
+slice.style = PathStyle::new().color(Color::Hex("#E1B31E"));
+assert!(slice.contains(&Point(60.0, 40.0)));
+
+let mut stroke = Arc::new(Point(50.0, 50.0), 45.0, 0.0, 270.0);
+stroke.style = PathStyle::new().stroke(Color::Hex("#000")).stroke_weight(2.0);
+```
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct Arc {
+    /// Center of the circle the arc is a part of.
+    pub center: Point,
+
+    /// Radius of the circle the arc is a part of.
+    pub radius: f64,
+
+    /// Where the arc starts, in degrees.
+    pub start_angle: f64,
+
+    /// Where the arc ends, in degrees.
+    pub end_angle: f64,
+
+    /// How the ends of the arc are connected.
+    pub kind: ArcKind,
+
+    /// Stroke width, stroke color and fill color.
+    pub style: PathStyle,
+
+    /// Applied on top of the arc when it has been stretched or skewed.
+    transform: Transform,
+}
+
+impl Arc {
+    /// An open arc, only the curve.
+    pub fn new(center: Point, radius: f64, start_angle: f64, end_angle: f64) -> Arc {
+        Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            kind: ArcKind::Open,
+            style: PathStyle::new(),
+            transform: Transform::identity(),
+        }
+    }
+
+    /// An arc closed by a straight line between its ends.
+    pub fn chord(center: Point, radius: f64, start_angle: f64, end_angle: f64) -> Arc {
+        Arc {
+            kind: ArcKind::Chord,
+            ..Arc::new(center, radius, start_angle, end_angle)
+        }
+    }
+
+    /// An arc closed by straight lines to the center, a slice of pie.
+    pub fn sector(center: Point, radius: f64, start_angle: f64, end_angle: f64) -> Arc {
+        Arc {
+            kind: ArcKind::Sector,
+            ..Arc::new(center, radius, start_angle, end_angle)
+        }
+    }
+
+    /// How many degrees the arc covers, negative for counterclockwise arcs and at most a
+    /// full circle.
+    pub fn sweep(&self) -> f64 {
+        (self.end_angle - self.start_angle).clamp(-360.0, 360.0)
+    }
+
+    /// The length of the curve.
+    pub fn length(&self) -> f64 {
+        self.sweep().abs().to_radians() * self.radius
+    }
+
+    /// The point on the circle at `angle` degrees, before the transform.
+    fn local_point(&self, angle: f64) -> Point {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        Point(
+            self.center.0 + self.radius * cos,
+            self.center.1 + self.radius * sin,
+        )
+    }
+
+    /// The outline of the arc, including the center for sectors, with the transform applied.
+    fn outline(&self) -> Vec<Point> {
+        let sweep = self.sweep();
+        let segments = (self.radius * sweep.abs().to_radians())
+            .clamp(4.0, 512.0)
+            .ceil() as usize;
+
+        let mut points = (0..=segments)
+            .map(|i| self.local_point(self.start_angle + sweep * i as f64 / segments as f64))
+            .collect::<Vec<Point>>();
+
+        if self.kind == ArcKind::Sector {
+            points.insert(0, self.center);
+        }
+
+        points
+            .iter()
+            .map(|point| self.transform.apply(point))
+            .collect()
+    }
+
+    /// The outline of the arc as it is stroked, closed unless the arc is open.
+    fn stroke_outline(&self) -> Vec<Point> {
+        let mut outline = self.outline();
+
+        if let (ArcKind::Chord | ArcKind::Sector, Some(first)) = (self.kind, outline.first()) {
+            outline.push(*first);
+        }
+
+        outline
+    }
+
+    /// True if `angle` is between the start and end angle.
+    fn covers(&self, angle: f64) -> bool {
+        let sweep = self.sweep();
+        if sweep.abs() >= 360.0 {
+            return true;
+        }
+
+        let (from, sweep) = if sweep < 0.0 {
+            (self.end_angle, -sweep)
+        } else {
+            (self.start_angle, sweep)
+        };
+
+        (angle - from).rem_euclid(360.0) <= sweep
+    }
+
+    /// The path data of the arc, using the SVG `A` command.
+    fn path_data(&self) -> String {
+        let sweep = self.sweep();
+        let start = self.local_point(self.start_angle);
+        let sweep_flag = if sweep > 0.0 { 1 } else { 0 };
+
+        let arc_to = |to: Point, large: bool| {
+            format!(
+                " A{r:.2},{r:.2} 0 {} {sweep_flag} {:.2},{:.2}",
+                if large { 1 } else { 0 },
+                to.0,
+                to.1,
+                r = self.radius
+            )
+        };
+
+        let mut data = match self.kind {
+            ArcKind::Sector => format!(
+                "M{:.2},{:.2} L{:.2},{:.2}",
+                self.center.0, self.center.1, start.0, start.1
+            ),
+            _ => format!("M{:.2},{:.2}", start.0, start.1),
+        };
+
+        // A single arc command can not draw a full circle, since the start and end are the
+        // same point, so full circles are split in two halves.
+        if sweep.abs() >= 360.0 {
+            data.push_str(&arc_to(
+                self.local_point(self.start_angle + sweep / 2.0),
+                false,
+            ));
+            data.push_str(&arc_to(start, false));
+        } else {
+            data.push_str(&arc_to(
+                self.local_point(self.end_angle),
+                sweep.abs() > 180.0,
+            ));
+        }
+
+        if self.kind != ArcKind::Open {
+            data.push_str(" Z");
+        }
+
+        data
+    }
+}
+
+impl Shape for Arc {
+    fn as_svg(&self) -> String {
+        let fill = match self.style.color {
+            Some(color) => format!(" fill=\"{color}\""),
+            None => String::from(" fill=\"none\""),
+        };
+
+        let stroke = match self.style.stroke {
+            Some(color) => format!(" stroke=\"{color}\""),
+            None => String::from(""),
+        };
+
+        let stroke_weight = match self.style.stroke_weight {
+            Some(weight) => format!(" stroke-width=\"{weight:.2}\""),
+            None => String::from(""),
+        };
+
+        let transform = if self.transform.is_identity() {
+            String::from("")
+        } else {
+            format!(" transform=\"{}\"", self.transform)
+        };
+
+        format!(
+            "<path d=\"{}\"{fill}{stroke}{stroke_weight}{transform}/>",
+            self.path_data()
+        )
+    }
+
+    fn render(&self, image: &mut RgbImage) {
+        if self.style.color.is_some() {
+            Polygon::new(self.outline(), self.style).render(image);
+        }
+
+        if self.style.stroke.is_some() || self.style.color.is_none() {
+            Path::new(self.stroke_outline(), self.style).render(image);
+        }
+    }
+
+    fn render_blended(&self, canvas: &mut Canvas) {
+        if let Some(color) = self.style.color {
+            canvas.fill_polygon(&self.outline(), color);
+        }
+
+        if let Some(color) = self.style.stroke {
+            canvas.stroke_polyline(
+                &self.stroke_outline(),
+                self.style.stroke_weight.unwrap_or(1.0),
+                color,
+            );
+        }
+    }
+
+    fn center(&self) -> Point {
+        self.transform.apply(&self.center)
+    }
+
+    fn bounding_box(&self) -> Option<Rectangle> {
+        let points = if self.transform.is_identity() {
+            // The ends, the points where the circle is furthest to each side, and the center
+            // for sectors.
+            let mut points = vec![
+                self.local_point(self.start_angle),
+                self.local_point(self.start_angle + self.sweep()),
+            ];
+            points.extend(
+                [0.0, 90.0, 180.0, 270.0]
+                    .into_iter()
+                    .filter(|angle| self.covers(*angle))
+                    .map(|angle| self.local_point(angle)),
+            );
+            if self.kind == ArcKind::Sector {
+                points.push(self.center);
+            }
+            points
+        } else {
+            self.outline()
+        };
+
+        let first = points.first()?;
+        let (min_x, min_y, max_x, max_y) = points.iter().fold(
+            (first.0, first.1, first.0, first.1),
+            |(x1, y1, x2, y2), point| {
+                (
+                    x1.min(point.0),
+                    y1.min(point.1),
+                    x2.max(point.0),
+                    y2.max(point.1),
+                )
+            },
+        );
+
+        Some(Rectangle::new(
+            Point(min_x, min_y),
+            max_x - min_x,
+            max_y - min_y,
+        ))
+    }
+
+    /// True if `point` is in the area the arc is filled in. Open arcs and chords cover the
+    /// area between the curve and the straight line between its ends.
+    fn contains(&self, point: &Point) -> bool {
+        let Some(inverse) = self.transform.invert() else {
+            return false;
+        };
+
+        let point = inverse.apply(point);
+        if self.center.distance_to(&point) > self.radius {
+            return false;
+        }
+
+        let angle = (point.1 - self.center.1)
+            .atan2(point.0 - self.center.0)
+            .to_degrees();
+        let in_sector = self.covers(angle);
+
+        if self.kind == ArcKind::Sector || self.sweep().abs() >= 360.0 {
+            return in_sector;
+        }
+
+        // The area between the curve and the chord is the sector without the triangle
+        // between the center and the ends, or with it for arcs larger than half a circle.
+        let triangle = Polygon::new(
+            vec![
+                self.center,
+                self.local_point(self.start_angle),
+                self.local_point(self.end_angle),
+            ],
+            PathStyle::new(),
+        );
+        let in_triangle = triangle.contains(&point);
+
+        if self.sweep().abs() <= 180.0 {
+            in_sector && !in_triangle
+        } else {
+            in_sector || in_triangle
+        }
+    }
+
+    /// Moving, rotating, mirroring and uniform scaling change the center, radius and angles,
+    /// other transforms are applied on top of the arc. The stroke width stays the same.
+    fn transform(&self, transform: &Transform) -> Arc {
+        let transform = self.transform.then(transform);
+
+        if !transform.is_similarity() {
+            return Arc { transform, ..*self };
+        }
+
+        let center = transform.apply(&self.center);
+        let start = transform.apply(&self.local_point(self.start_angle));
+        let start_angle = (start.1 - center.1).atan2(start.0 - center.0).to_degrees();
+        let sweep = self.sweep() * transform.determinant().signum();
+
+        Arc {
+            center,
+            radius: self.radius * transform.determinant().abs().sqrt(),
+            start_angle,
+            end_angle: start_angle + sweep,
+            transform: Transform::identity(),
+            ..*self
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        palette::color::Color,
+        raster::canvas::Canvas,
+        shapes::{path_style::PathStyle, point::Point, shape::Shape, transform::Transform},
+    };
+
+    use super::Arc;
+
+    #[test]
+    fn svg() {
+        let arc = Arc::new(Point(0.0, 0.0), 10.0, 0.0, 90.0);
+        assert_eq!(
+            arc.as_svg(),
+            "<path d=\"M10.00,0.00 A10.00,10.00 0 0 1 0.00,10.00\" fill=\"none\"/>"
+        );
+
+        let mut sector = Arc::sector(Point(0.0, 0.0), 10.0, 0.0, -270.0);
+        sector.style = PathStyle::new().color(Color::Hex("#f00"));
+        assert_eq!(
+            sector.as_svg(),
+            "<path d=\"M0.00,0.00 L10.00,0.00 A10.00,10.00 0 1 0 -0.00,10.00 Z\" fill=\"#f00\"/>"
+        );
+
+        let circle = Arc::chord(Point(0.0, 0.0), 10.0, 0.0, 360.0);
+        assert_eq!(
+            circle.as_svg(),
+            "<path d=\"M10.00,0.00 A10.00,10.00 0 0 1 -10.00,0.00 A10.00,10.00 0 0 1 10.00,0.00 Z\" fill=\"none\"/>"
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let center = Point(0.0, 0.0);
+
+        let sector = Arc::sector(center, 10.0, 0.0, 90.0);
+        assert!(sector.contains(&Point(2.0, 2.0)));
+        assert!(!sector.contains(&Point(-2.0, 2.0)));
+        assert!(!sector.contains(&Point(8.0, 8.0)));
+
+        let chord = Arc::chord(center, 10.0, 0.0, 90.0);
+        assert!(!chord.contains(&Point(2.0, 2.0)));
+        assert!(chord.contains(&Point(6.0, 6.0)));
+
+        let large = Arc::new(center, 10.0, 0.0, 270.0);
+        assert!(large.contains(&Point(2.0, 2.0)));
+        assert!(large.contains(&Point(1.0, -2.0)));
+        assert!(!large.contains(&Point(6.0, -6.0)));
+
+        let counterclockwise = Arc::sector(center, 10.0, 90.0, 0.0);
+        assert!(counterclockwise.contains(&Point(2.0, 2.0)));
+        assert!(!counterclockwise.contains(&Point(2.0, -2.0)));
+    }
+
+    #[test]
+    fn bounding_box() {
+        let arc = Arc::new(Point(0.0, 0.0), 10.0, -45.0, 45.0);
+        let bounds = arc.bounding_box().unwrap();
+        let half = 50f64.sqrt();
+
+        assert!(bounds.position.distance_to(&Point(half, -half)) < 1e-9);
+        assert!((bounds.width - (10.0 - half)).abs() < 1e-9);
+        assert!((bounds.height - 2.0 * half).abs() < 1e-9);
+
+        let sector = Arc::sector(Point(0.0, 0.0), 10.0, -45.0, 45.0);
+        assert_eq!(sector.bounding_box().unwrap().position.0, 0.0);
+        assert!((sector.bounding_box().unwrap().width - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn transform() {
+        let arc = Arc::sector(Point(10.0, 0.0), 5.0, 0.0, 90.0);
+
+        let moved = arc.transform(&Transform::rotate(90.0).then(&Transform::scale(2.0, 2.0)));
+        assert!(moved.center.distance_to(&Point(0.0, 20.0)) < 1e-9);
+        assert!((moved.radius - 10.0).abs() < 1e-9);
+        assert!((moved.start_angle - 90.0).abs() < 1e-9 && (moved.end_angle - 180.0).abs() < 1e-9);
+
+        let mirrored = arc.transform(&Transform::scale(-1.0, 1.0));
+        assert!(mirrored.contains(&Point(-12.0, 2.0)));
+        assert!(!mirrored.contains(&Point(-8.0, 2.0)));
+
+        let stretched = arc.transform(&Transform::scale(2.0, 1.0));
+        assert!(stretched.contains(&Point(26.0, 2.0)));
+        assert!(stretched
+            .as_svg()
+            .contains("transform=\"matrix(2 0 0 1 0 0)\""));
+    }
+
+    #[test]
+    fn render_blended() {
+        let mut canvas = Canvas::new(20, 20);
+        let mut sector = Arc::sector(Point(10.0, 10.0), 8.0, 0.0, 90.0);
+        sector.style = PathStyle::new().color(Color::Hex("#00f"));
+        sector.render_blended(&mut canvas);
+
+        assert_eq!(canvas.image().get_pixel(13, 13).0, [0, 0, 255, 255]);
+        assert_eq!(canvas.image().get_pixel(6, 13).0, [0, 0, 0, 0]);
+        assert_eq!(canvas.image().get_pixel(13, 6).0, [0, 0, 0, 0]);
+    }
+}
@@ -1,8 +1,8 @@
 use std::fmt::Display;
 
-use crate::palette::color::Color;
+use crate::{palette::color::Color, raster::canvas::Canvas};
 
-use super::{point::Point, rectangle::Rectangle, shape::Shape};
+use super::{point::Point, rectangle::Rectangle, shape::Shape, transform::Transform};
 
 /// A Circle
 #[derive(Clone, Copy, Debug)]
@@ -15,6 +15,10 @@ pub struct Circle {
 
     /// Fill color for a circle
     color: Option<Color>,
+
+    /// Applied on top of the center and radius when the circle has been stretched or
+    /// skewed into an ellipse.
+    transform: Transform,
 }
 
 impl Circle {
@@ -29,6 +33,7 @@ impl Circle {
             center,
             radius,
             color: None,
+            transform: Transform::identity(),
         }
     }
 
@@ -53,6 +58,21 @@ impl Circle {
         self.distance(other) < self.radius / 2. + other.radius / 2.
     }
 
+    /// The outline of the circle as a polygon, with the transform applied.
+    fn outline(&self) -> Vec<Point> {
+        let segments = (self.radius * 2.0).clamp(16.0, 256.0) as usize;
+
+        (0..segments)
+            .map(|i| {
+                let (sin, cos) = (std::f64::consts::TAU * i as f64 / segments as f64).sin_cos();
+                self.transform.apply(&Point(
+                    self.center.0 + self.radius * cos,
+                    self.center.1 + self.radius * sin,
+                ))
+            })
+            .collect()
+    }
+
     /// True if a given circle intersects any other circle in the, otherwise false
     pub fn instersects_any(&self, others: Vec<Circle>) -> bool {
         others.iter().any(|circle| self.intersects(circle))
@@ -69,6 +89,7 @@ impl Circle {
             radius: self.radius * scale,
             center: self.center,
             color: self.color,
+            transform: self.transform,
         }
     }
 }
@@ -80,9 +101,15 @@ impl Shape for Circle {
             _ => String::from("transparent"),
         };
 
+        let transform = if self.transform.is_identity() {
+            String::from("")
+        } else {
+            format!(" transform=\"{}\"", self.transform)
+        };
+
         format!(
-            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"{}\" />",
-            self.center.0, self.center.1, self.radius, fill
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"{}\"{} />",
+            self.center.0, self.center.1, self.radius, fill, transform
         )
     }
 
@@ -95,14 +122,47 @@ impl Shape for Circle {
             _ => Color::Hex("#000000"),
         };
 
+        if !self.transform.is_identity() {
+            let outline = self
+                .outline()
+                .iter()
+                .map(|point| imageproc::point::Point::new(point.0 as i32, point.1 as i32))
+                .collect::<Vec<_>>();
+            imageproc::drawing::draw_polygon_mut(image, &outline, color.into());
+            return;
+        }
+
         imageproc::drawing::draw_filled_circle_mut(image, center, radius, color.into());
     }
 
+    fn render_blended(&self, canvas: &mut Canvas) {
+        if let Some(color) = self.color {
+            if self.transform.is_identity() {
+                canvas.fill_circle(self.center, self.radius, color);
+            } else {
+                canvas.fill_polygon(&self.outline(), color);
+            }
+        }
+    }
+
     fn center(&self) -> Point {
-        Point(self.center.0, self.center.1)
+        self.transform.apply(&self.center)
     }
 
     fn bounding_box(&self) -> Option<Rectangle> {
+        if !self.transform.is_identity() {
+            // Half the width and height of the ellipse the circle has been transformed into.
+            let [a, b, c, d, ..] = self.transform.matrix();
+            let (width, height) = (self.radius * a.hypot(c), self.radius * b.hypot(d));
+            let center = self.center();
+
+            return Some(Rectangle::new(
+                Point(center.0 - width, center.1 - height),
+                width * 2.0,
+                height * 2.0,
+            ));
+        }
+
         Some(Rectangle {
             position: Point(self.center.0 - self.radius, self.center.1 - self.radius),
             width: self.center.0 + self.radius,
@@ -113,7 +173,27 @@ impl Shape for Circle {
     }
 
     fn contains(&self, point: &Point) -> bool {
-        self.center().distance_to(point) < self.radius
+        match self.transform.invert() {
+            Some(inverse) => self.center.distance_to(&inverse.apply(point)) < self.radius,
+            None => false,
+        }
+    }
+
+    /// Moves, rotates and uniformly scales the circle by changing its center and radius,
+    /// other transforms turn it into an ellipse.
+    fn transform(&self, transform: &Transform) -> Circle {
+        let transform = self.transform.then(transform);
+
+        if transform.is_similarity() {
+            Circle {
+                center: transform.apply(&self.center),
+                radius: self.radius * transform.determinant().abs().sqrt(),
+                color: self.color,
+                transform: Transform::identity(),
+            }
+        } else {
+            Circle { transform, ..*self }
+        }
     }
 }
 
@@ -141,6 +221,7 @@ impl Default for Circle {
             center: Point(0.0, 0.0),
             radius: 0.0,
             color: None,
+            transform: Transform::identity(),
         }
     }
 }
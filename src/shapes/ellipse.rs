@@ -0,0 +1,287 @@
+use image::RgbImage;
+
+use crate::raster::canvas::Canvas;
+
+use super::{
+    path_style::PathStyle, point::Point, polygon::Polygon, rectangle::Rectangle, shape::Shape,
+    transform::Transform,
+};
+
+/**
+An ellipse, a circle stretched along two axes that can be rotated. Like in SVG, the
+ellipse is filled black unless it has a fill color.
+
+Example
+
+```
+use art::{
+    palette::color::Color,
+    shapes::{ellipse::Ellipse, path_style::PathStyle, point::Point, shape::Shape},
+};
+
+let mut ellipse = Ellipse::new(Point(50.0, 50.0), 40.0, 20.0);
+ellipse.rotation = 30.0;
+ellipse.style = PathStyle::new().color(Color::Hex("#E1B31E"));
+
+assert!(ellipse.contains(&Point(80.0, 65.0)));
+```
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct Ellipse {
+    /// Center point of the ellipse.
+    pub center: Point,
+
+    /// Radius along the horizontal axis, before rotating.
+    pub radius_x: f64,
+
+    /// Radius along the vertical axis, before rotating.
+    pub radius_y: f64,
+
+    /// Rotation in degrees around the center, clockwise since the y axis points down.
+    pub rotation: f64,
+
+    /// Stroke width, stroke color and fill color.
+    pub style: PathStyle,
+}
+
+impl Ellipse {
+    /// Create a new, unrotated, Ellipse at `center` with the given radii.
+    pub fn new(center: Point, radius_x: f64, radius_y: f64) -> Ellipse {
+        Ellipse {
+            center,
+            radius_x,
+            radius_y,
+            rotation: 0.0,
+            style: PathStyle::new(),
+        }
+    }
+
+    /// Surface area of the ellipse.
+    pub fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius_x * self.radius_y
+    }
+
+    /// The point on the outline at `angle` degrees, measured clockwise from the horizontal
+    /// axis of the ellipse before it is rotated.
+    pub fn point_at(&self, angle: f64) -> Point {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        self.local_point(self.radius_x * cos, self.radius_y * sin)
+    }
+
+    /// The outline of the ellipse as a [`Polygon`] with the same style, with enough corners
+    /// that the edges can not be seen.
+    pub fn to_polygon(&self) -> Polygon {
+        let segments = (self.radius_x.max(self.radius_y) * 2.0).clamp(16.0, 512.0) as usize;
+        let points = (0..segments)
+            .map(|i| self.point_at(360.0 * i as f64 / segments as f64))
+            .collect();
+
+        Polygon::new(points, self.style)
+    }
+
+    /// Canvas coordinates of a point given relative to the unrotated ellipse.
+    fn local_point(&self, x: f64, y: f64) -> Point {
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        Point(
+            self.center.0 + x * cos - y * sin,
+            self.center.1 + x * sin + y * cos,
+        )
+    }
+}
+
+impl Shape for Ellipse {
+    fn as_svg(&self) -> String {
+        let fill = match self.style.color {
+            Some(color) => format!(" fill=\"{color}\""),
+            None => String::from(""),
+        };
+
+        let stroke = match self.style.stroke {
+            Some(color) => format!(" stroke=\"{color}\""),
+            None => String::from(""),
+        };
+
+        let stroke_weight = match self.style.stroke_weight {
+            Some(weight) => format!(" stroke-width=\"{weight:.2}\""),
+            None => String::from(""),
+        };
+
+        let transform = Transform::rotate_around(self.rotation, self.center);
+        let transform = if transform.is_identity() {
+            String::from("")
+        } else {
+            format!(" transform=\"{transform}\"")
+        };
+
+        format!(
+            "<ellipse cx=\"{:.2}\" cy=\"{:.2}\" rx=\"{:.2}\" ry=\"{:.2}\"{fill}{stroke}{stroke_weight}{transform}/>",
+            self.center.0, self.center.1, self.radius_x, self.radius_y
+        )
+    }
+
+    fn render(&self, image: &mut RgbImage) {
+        self.to_polygon().render(image);
+    }
+
+    fn render_blended(&self, canvas: &mut Canvas) {
+        self.to_polygon().render_blended(canvas);
+    }
+
+    fn center(&self) -> Point {
+        self.center
+    }
+
+    fn bounding_box(&self) -> Option<Rectangle> {
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let width = (self.radius_x * cos).hypot(self.radius_y * sin);
+        let height = (self.radius_x * sin).hypot(self.radius_y * cos);
+
+        Some(Rectangle::new(
+            Point(self.center.0 - width, self.center.1 - height),
+            width * 2.0,
+            height * 2.0,
+        ))
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let (dx, dy) = (point.0 - self.center.0, point.1 - self.center.1);
+        let (x, y) = (dx * cos + dy * sin, -dx * sin + dy * cos);
+
+        (x / self.radius_x).powi(2) + (y / self.radius_y).powi(2) <= 1.0
+    }
+
+    /// The transformed ellipse is still an ellipse, so this is exact. The stroke width stays
+    /// the same.
+    fn transform(&self, transform: &Transform) -> Ellipse {
+        // The columns of `m` are the two axes of the ellipse after transforming.
+        let [a, b, c, d, ..] = transform.matrix();
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let (axis_x, axis_y) = (
+            (self.radius_x * cos, self.radius_x * sin),
+            (-self.radius_y * sin, self.radius_y * cos),
+        );
+        let m = [
+            [a * axis_x.0 + c * axis_x.1, a * axis_y.0 + c * axis_y.1],
+            [b * axis_x.0 + d * axis_x.1, b * axis_y.0 + d * axis_y.1],
+        ];
+
+        // The radii and rotation are the square roots of the eigenvalues and the direction of
+        // the eigenvectors of m * mᵀ.
+        let p = m[0][0].powi(2) + m[0][1].powi(2);
+        let q = m[0][0] * m[1][0] + m[0][1] * m[1][1];
+        let r = m[1][0].powi(2) + m[1][1].powi(2);
+        let mean = (p + r) / 2.0;
+        let spread = ((p - r) / 2.0).hypot(q);
+
+        Ellipse {
+            center: transform.apply(&self.center),
+            radius_x: (mean + spread).sqrt(),
+            radius_y: (mean - spread).max(0.0).sqrt(),
+            rotation: (0.5 * (2.0 * q).atan2(p - r)).to_degrees(),
+            style: self.style,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        palette::color::Color,
+        raster::canvas::Canvas,
+        shapes::{path_style::PathStyle, point::Point, shape::Shape, transform::Transform},
+    };
+
+    use super::Ellipse;
+
+    #[test]
+    fn contains() {
+        let mut ellipse = Ellipse::new(Point(0.0, 0.0), 10.0, 5.0);
+
+        assert!(ellipse.contains(&Point(9.0, 0.0)));
+        assert!(!ellipse.contains(&Point(0.0, 6.0)));
+
+        ellipse.rotation = 90.0;
+        assert!(!ellipse.contains(&Point(9.0, 0.0)));
+        assert!(ellipse.contains(&Point(0.0, 9.0)));
+    }
+
+    #[test]
+    fn bounding_box() {
+        let mut ellipse = Ellipse::new(Point(10.0, 10.0), 10.0, 5.0);
+        let bounds = ellipse.bounding_box().unwrap();
+        assert_eq!(
+            (bounds.position, bounds.width, bounds.height),
+            (Point(0.0, 5.0), 20.0, 10.0)
+        );
+
+        ellipse.rotation = 45.0;
+        let bounds = ellipse.bounding_box().unwrap();
+        let expected = (125.0f64 / 2.0).sqrt();
+        assert!((bounds.width / 2.0 - expected).abs() < 1e-9);
+        assert!((bounds.height / 2.0 - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn svg() {
+        let mut ellipse = Ellipse::new(Point(10.0, 20.0), 5.0, 2.0);
+        ellipse.style = PathStyle::new().color(Color::Hex("#f00"));
+        assert_eq!(
+            ellipse.as_svg(),
+            "<ellipse cx=\"10.00\" cy=\"20.00\" rx=\"5.00\" ry=\"2.00\" fill=\"#f00\"/>"
+        );
+
+        ellipse.rotation = 30.0;
+        assert!(ellipse
+            .as_svg()
+            .ends_with(" transform=\"matrix(0.866025 0.5 -0.5 0.866025 11.339746 -2.320508)\"/>"));
+
+        ellipse.rotation = 360.0;
+        assert!(!ellipse.as_svg().contains("transform"));
+    }
+
+    #[test]
+    fn transform() {
+        let ellipse = Ellipse::new(Point(10.0, 0.0), 4.0, 2.0);
+
+        let rotated = ellipse.transform(&Transform::rotate(90.0));
+        assert!(rotated.center.distance_to(&Point(0.0, 10.0)) < 1e-9);
+        assert!((rotated.radius_x - 4.0).abs() < 1e-9 && (rotated.radius_y - 2.0).abs() < 1e-9);
+        assert!((rotated.rotation.abs() - 90.0).abs() < 1e-9);
+
+        let skewed = Ellipse {
+            rotation: 20.0,
+            ..ellipse
+        }
+        .transform(&Transform::skew_x(30.0).then(&Transform::scale(1.5, 0.5)));
+        for angle in [0.0, 45.0, 123.0, 300.0] {
+            let point = Transform::skew_x(30.0)
+                .then(&Transform::scale(1.5, 0.5))
+                .apply(
+                    &Ellipse {
+                        rotation: 20.0,
+                        ..ellipse
+                    }
+                    .point_at(angle),
+                );
+
+            // Every transformed point of the outline is on the outline of the result.
+            let (sin, cos) = skewed.rotation.to_radians().sin_cos();
+            let (dx, dy) = (point.0 - skewed.center.0, point.1 - skewed.center.1);
+            let (x, y) = (dx * cos + dy * sin, -dx * sin + dy * cos);
+            let distance = (x / skewed.radius_x).powi(2) + (y / skewed.radius_y).powi(2);
+            assert!((distance - 1.0).abs() < 1e-9, "{distance}");
+        }
+    }
+
+    #[test]
+    fn render_blended() {
+        let mut canvas = Canvas::new(20, 20);
+        let mut ellipse = Ellipse::new(Point(10.0, 10.0), 8.0, 3.0);
+        ellipse.style = PathStyle::new().color(Color::Hex("#00f"));
+        ellipse.render_blended(&mut canvas);
+
+        assert_eq!(canvas.image().get_pixel(16, 10).0, [0, 0, 255, 255]);
+        assert_eq!(canvas.image().get_pixel(10, 15).0, [0, 0, 0, 0]);
+    }
+}
@@ -1,6 +1,13 @@
+pub mod arc;
 pub mod circle;
+pub mod ellipse;
 pub mod path;
 pub mod path_style;
 pub mod point;
+pub mod polygon;
 pub mod rectangle;
+pub mod segment;
 pub mod shape;
+mod simplify;
+mod smoothing;
+pub mod transform;
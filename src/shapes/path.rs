@@ -1,16 +1,44 @@
-use super::{path_style::PathStyle, point::Point, rectangle::Rectangle, shape::Shape};
+use crate::raster::canvas::Canvas;
 
-/// An SVG path
+use super::{
+    path_style::PathStyle, point::Point, rectangle::Rectangle, segment::Segment, shape::Shape,
+    transform::Transform,
+};
+
+/// How far, in pixels, flattened curves may be from the real curve when rendering.
+const TOLERANCE: f64 = 0.1;
+
+/// How far flattened curves may be from the real curve when measuring the length.
+const LENGTH_TOLERANCE: f64 = 0.001;
+
+/**
+An SVG path, made of straight lines and quadratic or cubic Bezier curves.
+
+Example
+
+```
+use art::shapes::{path::Path, path_style::PathStyle, point::Point, shape::Shape};
+
+let mut path = Path::new(vec![Point(0.0, 0.0), Point(10.0, 0.0)], PathStyle::new());
+path.quadratic_to(Point(15.0, 0.0), Point(15.0, 5.0));
+path.cubic_to(Point(15.0, 10.0), Point(5.0, 10.0), Point(0.0, 10.0));
+
+assert!(path.as_svg().contains("Q15.00,0.00 15.00,5.00"));
+```
+*/
 #[derive(Debug, Clone)]
 pub struct Path {
-    /// List of points that make up the path.
-    pub points: Vec<Point>,
+    /// List of points that make up the path. Private, like `segments`, so the two always
+    /// line up.
+    points: Vec<Point>,
+
+    /// How each point connects to the next one, `segments[i]` goes from `points[i]` to
+    /// `points[i + 1]`. Points without a segment are connected by a straight line. Private
+    /// so curves can only be added together with their end point.
+    segments: Vec<Segment>,
 
     /// Stroke width, stroke color and fill color.
     pub style: PathStyle,
-
-    rotation: Option<f64>,
-    rotation_center: Option<Point>,
 }
 
 impl Path {
@@ -18,36 +46,82 @@ impl Path {
     pub fn new(points: Vec<Point>, style: PathStyle) -> Path {
         Path {
             points,
+            segments: vec![],
             style,
-            rotation: None,
-            rotation_center: None,
         }
     }
 
     /// Adds another [`Point`] to the end of this path. This is good if
     /// You want to make a line longer.
     pub fn add_point(&mut self, point: Point) {
+        self.push(Segment::Line, point);
+    }
+
+    /// Adds a quadratic Bezier curve from the last point to `to`, bending towards `control`.
+    /// On an empty path, `to` becomes the first point.
+    pub fn quadratic_to(&mut self, control: Point, to: Point) {
+        self.push(Segment::Quadratic(control), to);
+    }
+
+    /// Adds a cubic Bezier curve from the last point to `to`. The curve leaves the last point
+    /// towards `control_1` and arrives at `to` coming from `control_2`. On an empty path, `to`
+    /// becomes the first point.
+    pub fn cubic_to(&mut self, control_1: Point, control_2: Point, to: Point) {
+        self.push(Segment::Cubic(control_1, control_2), to);
+    }
+
+    /// The points that make up the path, the ends of every line and curve.
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    /// How `points[index]` connects to the point after it.
+    pub fn segment(&self, index: usize) -> Segment {
+        self.segments.get(index).copied().unwrap_or_default()
+    }
+
+    /// How each point connects to the next one, one [`Segment`] less than there are points.
+    pub fn segments(&self) -> Vec<Segment> {
+        (0..self.points.len().saturating_sub(1))
+            .map(|index| self.segment(index))
+            .collect()
+    }
+
+    /// The path as straight lines only, with curves approximated so that no point of the
+    /// curve is further than about `tolerance` from the lines.
+    pub fn flatten(&self, tolerance: f64) -> Vec<Point> {
+        let mut points = self.points.first().into_iter().copied().collect::<Vec<_>>();
+
+        for (i, pair) in self.points.windows(2).enumerate() {
+            self.segment(i)
+                .flatten(&pair[0], &pair[1], tolerance, &mut points);
+        }
+
+        points
+    }
+
+    fn push(&mut self, segment: Segment, point: Point) {
+        if !self.points.is_empty() {
+            self.segments.resize(self.points.len() - 1, Segment::Line);
+            self.segments.push(segment);
+        }
+
         self.points.push(point);
     }
 
+    /// Rotate every point of the path by `angle` degrees around `center`.
     pub fn rotate(&mut self, angle: f64, center: Point) -> &Path {
-        self.rotation = Some(angle);
-        self.rotation_center = Some(center);
+        *self = self.transform(&Transform::rotate_around(angle, center));
         self
     }
 
     /// The total distance between each point in this shape, i.e, the true
-    /// length of the shape.
+    /// length of the shape, following the curves.
     pub fn length(&self) -> f64 {
-        if self.points.is_empty() {
-            return 0.0;
-        }
-
-        let mut total = 0.0;
-        for i in 1..self.points.len() {
-            total += self.points[i - 1].distance_to(&self.points[i])
-        }
-        total
+        self.flatten(LENGTH_TOLERANCE)
+            .windows(2)
+            .map(|pair| pair[0].distance_to(&pair[1]))
+            .sum()
     }
 
     /// Check if two lines intersect at any point.
@@ -93,7 +167,22 @@ impl Shape for Path {
                 ),
                 |mut path, (i, point)| {
                     if let Some(previous) = self.points.get(i) {
-                        if previous.0 == point.0 {
+                        if let Segment::Quadratic(control) = self.segment(i) {
+                            path.push_str(&format!(
+                                " Q{:.2},{:.2} {:.2},{:.2}",
+                                control.0, control.1, point.0, point.1
+                            ));
+                        } else if let Segment::Cubic(control_1, control_2) = self.segment(i) {
+                            path.push_str(&format!(
+                                " C{:.2},{:.2} {:.2},{:.2} {:.2},{:.2}",
+                                control_1.0,
+                                control_1.1,
+                                control_2.0,
+                                control_2.1,
+                                point.0,
+                                point.1
+                            ));
+                        } else if previous.0 == point.0 {
                             path.push_str(&format!(" V{:.2}", point.1));
                         } else if previous.1 == point.1 {
                             path.push_str(&format!(" H{:.2}", point.0));
@@ -118,9 +207,8 @@ impl Shape for Path {
             None => image::Rgb([0, 0, 0]),
         };
 
-        for i in 0..self.points.len() - 1 {
-            let start = self.points.get(i).unwrap();
-            let end = self.points.get(i + 1).unwrap_or(start);
+        for line in self.flatten(TOLERANCE).windows(2) {
+            let (start, end) = (&line[0], &line[1]);
 
             imageproc::drawing::draw_line_segment_mut(
                 image,
@@ -131,6 +219,18 @@ impl Shape for Path {
         }
     }
 
+    fn render_blended(&self, canvas: &mut Canvas) {
+        let points = self.flatten(TOLERANCE);
+
+        if let Some(color) = self.style.color {
+            canvas.fill_polygon(&points, color);
+        }
+
+        if let Some(color) = self.style.stroke {
+            canvas.stroke_polyline(&points, self.style.stroke_weight.unwrap_or(1.0), color);
+        }
+    }
+
     fn center(&self) -> Point {
         if let Some(bounding) = self.bounding_box() {
             bounding.center();
@@ -144,24 +244,31 @@ impl Shape for Path {
             return None;
         }
 
-        let p = self.points.get(0)?;
+        let p = self.points.first()?;
 
         let min_x = p.0;
         let min_y = p.1;
         let max_x = min_x;
         let max_y = min_y;
 
-        let bounding =
-            self.points
-                .iter()
-                .fold((min_x, min_y, max_x, max_y), |(x1, y1, x2, y2), point| {
-                    (
-                        x1.min(point.0),
-                        y1.min(point.1),
-                        x2.max(point.0),
-                        y2.max(point.1),
-                    )
-                });
+        // Curves can bulge out past their ends, so their turning points count as well.
+        let extrema = self
+            .points
+            .windows(2)
+            .enumerate()
+            .flat_map(|(i, pair)| self.segment(i).extrema(&pair[0], &pair[1]));
+
+        let bounding = self.points.iter().copied().chain(extrema).fold(
+            (min_x, min_y, max_x, max_y),
+            |(x1, y1, x2, y2), point| {
+                (
+                    x1.min(point.0),
+                    y1.min(point.1),
+                    x2.max(point.0),
+                    y2.max(point.1),
+                )
+            },
+        );
 
         Some(Rectangle::new(
             Point(bounding.0, bounding.1),
@@ -176,7 +283,7 @@ impl Shape for Path {
     to the bounding box.
 
     It then takes two pairs of points (in other words a line) and checks how many
-    times each search ray intersects with each line,
+    times each search ray intersects with each line, curves are flattened into lines first,
     if the intersection count is even, then the point is inside the polygon,
     if the intersection count is uneven, then the point is outside the polygon.
     **Note:** this is for each search ray, so if any ray has uneven hits
@@ -215,14 +322,16 @@ impl Shape for Path {
             (point, &Point(bounds.position.0, point.1)),
         ];
 
+        let points = self.flatten(TOLERANCE);
+
         for ray in search_rays {
             let mut intersections = 0;
 
-            for i in 0..self.points.len() {
-                match self.points.get(i + 1) {
+            for i in 0..points.len() {
+                match points.get(i + 1) {
                     None => break,
                     Some(_) => {
-                        let line = (&self.points[i], &self.points[i + 1]);
+                        let line = (&points[i], &points[i + 1]);
                         if Path::intersects(line, ray) {
                             intersections += 1;
                         }
@@ -236,4 +345,137 @@ impl Shape for Path {
         }
         true
     }
+
+    /// Transforms the points and curves of the path, the stroke width stays the same.
+    fn transform(&self, transform: &Transform) -> Path {
+        Path {
+            points: self
+                .points
+                .iter()
+                .map(|point| transform.apply(point))
+                .collect(),
+            segments: self
+                .segments
+                .iter()
+                .map(|segment| segment.transform(transform))
+                .collect(),
+            style: self.style,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        palette::color::Color,
+        raster::canvas::Canvas,
+        shapes::{
+            path_style::PathStyle, point::Point, segment::Segment, shape::Shape,
+            transform::Transform,
+        },
+    };
+
+    use super::Path;
+
+    fn arch() -> Path {
+        let mut path = Path::new(vec![Point(0.0, 0.0)], PathStyle::new());
+        path.cubic_to(Point(0.0, -40.0), Point(40.0, -40.0), Point(40.0, 0.0));
+        path
+    }
+
+    #[test]
+    fn svg() {
+        let mut path = Path::new(vec![Point(0.0, 0.0), Point(10.0, 0.0)], PathStyle::new());
+        path.quadratic_to(Point(20.0, 0.0), Point(20.0, 10.0));
+        path.cubic_to(Point(20.0, 20.0), Point(0.0, 20.0), Point(0.0, 10.0));
+        path.add_point(Point(0.0, 0.0));
+
+        assert_eq!(
+            path.as_svg(),
+            "<path fill=\"none\" d=\"M0.00,0.00 H10.00 Q20.00,0.00 20.00,10.00 C20.00,20.00 0.00,20.00 0.00,10.00 V0.00\"/>\n"
+        );
+        assert_eq!(
+            path.segments(),
+            vec![
+                Segment::Line,
+                Segment::Quadratic(Point(20.0, 0.0)),
+                Segment::Cubic(Point(20.0, 20.0), Point(0.0, 20.0)),
+                Segment::Line
+            ]
+        );
+    }
+
+    #[test]
+    fn curve_on_empty_path() {
+        let mut path = Path::new(vec![], PathStyle::new());
+        path.quadratic_to(Point(5.0, 5.0), Point(10.0, 0.0));
+
+        assert_eq!(path.points(), vec![Point(10.0, 0.0)]);
+        assert!(path.segments().is_empty());
+    }
+
+    #[test]
+    fn length() {
+        let line = Path::new(
+            vec![Point(0.0, 0.0), Point(3.0, 4.0), Point(3.0, 10.0)],
+            PathStyle::new(),
+        );
+        assert_eq!(line.length(), 11.0);
+
+        // A quarter circle drawn with the usual cubic approximation.
+        let k = 0.5522847498 * 10.0;
+        let mut quarter = Path::new(vec![Point(10.0, 0.0)], PathStyle::new());
+        quarter.cubic_to(Point(10.0, k), Point(k, 10.0), Point(0.0, 10.0));
+        assert!((quarter.length() - std::f64::consts::FRAC_PI_2 * 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn bounding_box() {
+        let bounds = arch().bounding_box().unwrap();
+
+        assert_eq!(bounds.position, Point(0.0, -30.0));
+        assert_eq!((bounds.width, bounds.height), (40.0, 30.0));
+    }
+
+    #[test]
+    fn contains() {
+        let mut path = arch();
+        path.add_point(Point(0.0, 0.0));
+
+        assert!(path.contains(&Point(15.0, -20.0)));
+        assert!(!path.contains(&Point(2.0, -25.0)));
+    }
+
+    #[test]
+    fn transform() {
+        let moved = arch().transform(&Transform::translate(10.0, 0.0));
+
+        assert_eq!(moved.points(), vec![Point(10.0, 0.0), Point(50.0, 0.0)]);
+        assert_eq!(
+            moved.segment(0),
+            Segment::Cubic(Point(10.0, -40.0), Point(50.0, -40.0))
+        );
+    }
+
+    #[test]
+    fn render_blended() {
+        let mut canvas = Canvas::new(50, 40);
+        let mut path = arch().transform(&Transform::translate(5.0, 35.0));
+        path.style = PathStyle::new().color(Color::Hex("#ff0000"));
+        path.render_blended(&mut canvas);
+
+        assert_eq!(canvas.image().get_pixel(25, 15).0, [255, 0, 0, 255]);
+        assert_eq!(canvas.image().get_pixel(7, 8).0, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn render_blended_empty() {
+        let mut canvas = Canvas::new(10, 10);
+        let style = PathStyle::new()
+            .color(Color::Hex("#ff0000"))
+            .stroke(Color::Hex("#ff0000"));
+        Path::new(vec![], style).render_blended(&mut canvas);
+
+        assert!(canvas.image().pixels().all(|pixel| pixel.0 == [0, 0, 0, 0]));
+    }
 }
@@ -1,6 +1,8 @@
 use std::fmt::Display;
 
-use super::{rectangle::Rectangle, shape::Shape};
+use crate::{palette::color::Color, raster::canvas::Canvas};
+
+use super::{rectangle::Rectangle, shape::Shape, transform::Transform};
 
 /**
 A single point in the canvas.
@@ -83,6 +85,14 @@ impl Shape for Point {
         image.put_pixel(self.0 as u32, self.1 as u32, image::Rgb([255, 255, 255]));
     }
 
+    fn render_blended(&self, canvas: &mut Canvas) {
+        canvas.blend_pixel(
+            self.0.floor() as i64,
+            self.1.floor() as i64,
+            Color::Hex("#ffffff"),
+        );
+    }
+
     fn bounding_box(&self) -> Option<Rectangle> {
         Some(Rectangle {
             position: Point(self.0, self.1),
@@ -96,6 +106,10 @@ impl Shape for Point {
     fn contains(&self, point: &Point) -> bool {
         self.eq(point)
     }
+
+    fn transform(&self, transform: &Transform) -> Point {
+        transform.apply(self)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,394 @@
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+use image::RgbImage;
+
+use crate::{palette::color::Color, raster::canvas::Canvas};
+
+use super::{
+    path_style::PathStyle, point::Point, rectangle::Rectangle, shape::Shape, transform::Transform,
+};
+
+/**
+A closed polygon. The last point is connected back to the first point, so it should not
+be repeated. Like in SVG, the polygon is filled black unless it has a fill color, and
+self-intersecting polygons are filled using the nonzero winding rule.
+
+Example
+
+```
+use art::{
+    palette::color::Color,
+    shapes::{path_style::PathStyle, point::Point, polygon::Polygon, shape::Shape},
+};
+
+let mut hexagon = Polygon::regular(Point(50.0, 50.0), 20.0, 6);
+hexagon.style = PathStyle::new().color(Color::Hex("#E1B31E"));
+
+let mut star = Polygon::star(Point(50.0, 50.0), 40.0, 15.0, 5);
+star.style = PathStyle::new().stroke(Color::Hex("#000")).stroke_weight(2.0);
+
+let triangle = Polygon::new(
+    vec![Point(0.0, 0.0), Point(10.0, 0.0), Point(0.0, 10.0)],
+    PathStyle::new(),
+);
+assert!(triangle.contains(&Point(2.0, 2.0)));
+```
+*/
+#[derive(Debug, Clone)]
+pub struct Polygon {
+    /// The corners of the polygon, in order.
+    pub points: Vec<Point>,
+
+    /// Stroke width, stroke color and fill color.
+    pub style: PathStyle,
+}
+
+impl Polygon {
+    /// Create a new [`Polygon`] with the given corners and [`PathStyle`].
+    pub fn new(points: Vec<Point>, style: PathStyle) -> Polygon {
+        Polygon { points, style }
+    }
+
+    /// A polygon with `sides` sides of equal length, with its corners on a circle around
+    /// `center`. The first corner points straight up.
+    pub fn regular(center: Point, radius: f64, sides: usize) -> Polygon {
+        let points = (0..sides)
+            .map(|i| corner(center, radius, TAU * i as f64 / sides as f64))
+            .collect();
+
+        Polygon::new(points, PathStyle::new())
+    }
+
+    /// A star with `points` points, alternating between corners on a circle with
+    /// `outer_radius` and corners on a circle with `inner_radius`. The first point of the
+    /// star points straight up.
+    pub fn star(center: Point, outer_radius: f64, inner_radius: f64, points: usize) -> Polygon {
+        let points = (0..points * 2)
+            .map(|i| {
+                let radius = if i % 2 == 0 {
+                    outer_radius
+                } else {
+                    inner_radius
+                };
+                corner(center, radius, PI * i as f64 / points as f64)
+            })
+            .collect();
+
+        Polygon::new(points, PathStyle::new())
+    }
+
+    /// The area enclosed by the polygon. The area is positive when the points go clockwise
+    /// on screen and negative when they go counterclockwise.
+    pub fn signed_area(&self) -> f64 {
+        self.edges()
+            .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+            .sum::<f64>()
+            / 2.0
+    }
+
+    /// The area enclosed by the polygon.
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// The center of mass of the polygon. For polygons without area, this is the average of
+    /// the points.
+    pub fn centroid(&self) -> Point {
+        let area = self.signed_area();
+
+        if area.abs() < 1e-12 {
+            let count = self.points.len().max(1) as f64;
+            let (x, y) = self
+                .points
+                .iter()
+                .fold((0.0, 0.0), |(x, y), point| (x + point.0, y + point.1));
+            return Point(x / count, y / count);
+        }
+
+        let (x, y) = self.edges().fold((0.0, 0.0), |(x, y), (a, b)| {
+            let cross = a.0 * b.1 - b.0 * a.1;
+            (x + (a.0 + b.0) * cross, y + (a.1 + b.1) * cross)
+        });
+
+        Point(x / (6.0 * area), y / (6.0 * area))
+    }
+
+    /**
+    How many times the outline of the polygon winds around `point`, `0` if the point is
+    outside. Unlike counting ray crossings, this works for concave and self-intersecting
+    polygons, and is what the nonzero fill rule is based on.
+    */
+    pub fn winding_number(&self, point: &Point) -> i32 {
+        let side =
+            |a: &Point, b: &Point| (b.0 - a.0) * (point.1 - a.1) - (point.0 - a.0) * (b.1 - a.1);
+
+        self.edges()
+            .map(|(a, b)| {
+                if a.1 <= point.1 {
+                    if b.1 > point.1 && side(a, b) > 0.0 {
+                        return 1;
+                    }
+                } else if b.1 <= point.1 && side(a, b) < 0.0 {
+                    return -1;
+                }
+                0
+            })
+            .sum()
+    }
+
+    /// Every edge of the polygon, including the one from the last point back to the first.
+    fn edges(&self) -> impl Iterator<Item = (&Point, &Point)> {
+        self.points
+            .iter()
+            .zip(self.points.iter().cycle().skip(1))
+            .take(self.points.len())
+    }
+}
+
+/// The point at `radius` from `center`, `angle` radians clockwise from straight up.
+fn corner(center: Point, radius: f64, angle: f64) -> Point {
+    let (sin, cos) = (angle - FRAC_PI_2).sin_cos();
+    Point(center.0 + radius * cos, center.1 + radius * sin)
+}
+
+impl Shape for Polygon {
+    fn as_svg(&self) -> String {
+        if self.points.is_empty() {
+            return String::from("");
+        }
+
+        let points = self
+            .points
+            .iter()
+            .map(|point| format!("{:.2},{:.2}", point.0, point.1))
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        let fill = match self.style.color {
+            Some(color) => format!(" fill=\"{color}\""),
+            None => String::from(""),
+        };
+
+        let stroke = match self.style.stroke {
+            Some(color) => format!(" stroke=\"{color}\""),
+            None => String::from(""),
+        };
+
+        let stroke_weight = match self.style.stroke_weight {
+            Some(weight) => format!(" stroke-width=\"{weight:.2}\""),
+            None => String::from(""),
+        };
+
+        format!("<polygon points=\"{points}\"{fill}{stroke}{stroke_weight}/>")
+    }
+
+    fn render(&self, image: &mut RgbImage) {
+        let mut points = self
+            .points
+            .iter()
+            .map(|point| imageproc::point::Point::new(point.0 as i32, point.1 as i32))
+            .collect::<Vec<_>>();
+        points.dedup();
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+
+        if points.len() < 3 {
+            return;
+        }
+
+        let color = self.style.color.unwrap_or(Color::Hex("#000000"));
+        imageproc::drawing::draw_polygon_mut(image, &points, color.into());
+    }
+
+    fn render_blended(&self, canvas: &mut Canvas) {
+        // Nothing to fill or outline without at least two corners.
+        if self.points.len() < 2 {
+            return;
+        }
+
+        canvas.fill_polygon(
+            &self.points,
+            self.style.color.unwrap_or(Color::Hex("#000000")),
+        );
+
+        if let (Some(color), Some(first)) = (self.style.stroke, self.points.first()) {
+            let mut outline = self.points.clone();
+            outline.push(*first);
+            canvas.stroke_polyline(&outline, self.style.stroke_weight.unwrap_or(1.0), color);
+        }
+    }
+
+    fn center(&self) -> Point {
+        self.centroid()
+    }
+
+    fn bounding_box(&self) -> Option<Rectangle> {
+        let first = self.points.first()?;
+
+        let (min_x, min_y, max_x, max_y) = self.points.iter().fold(
+            (first.0, first.1, first.0, first.1),
+            |(x1, y1, x2, y2), point| {
+                (
+                    x1.min(point.0),
+                    y1.min(point.1),
+                    x2.max(point.0),
+                    y2.max(point.1),
+                )
+            },
+        );
+
+        Some(Rectangle::new(
+            Point(min_x, min_y),
+            max_x - min_x,
+            max_y - min_y,
+        ))
+    }
+
+    /// True if `point` is inside the polygon according to the nonzero winding rule.
+    fn contains(&self, point: &Point) -> bool {
+        self.winding_number(point) != 0
+    }
+
+    /// Transforms the corners of the polygon, the stroke width stays the same.
+    fn transform(&self, transform: &Transform) -> Polygon {
+        Polygon {
+            points: self
+                .points
+                .iter()
+                .map(|point| transform.apply(point))
+                .collect(),
+            style: self.style,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        palette::color::Color,
+        raster::canvas::Canvas,
+        shapes::{path_style::PathStyle, point::Point, shape::Shape},
+    };
+
+    use super::Polygon;
+
+    fn assert_close(a: Point, b: Point) {
+        assert!(a.distance_to(&b) < 1e-9, "{a} != {b}");
+    }
+
+    #[test]
+    fn regular() {
+        let hexagon = Polygon::regular(Point(0.0, 0.0), 10.0, 6);
+
+        assert_eq!(hexagon.points.len(), 6);
+        assert_close(hexagon.points[0], Point(0.0, -10.0));
+        assert!((hexagon.area() - 1.5 * 3f64.sqrt() * 100.0).abs() < 1e-9);
+        assert_close(hexagon.centroid(), Point(0.0, 0.0));
+        assert!(hexagon.contains(&Point(0.0, 9.0)));
+        assert!(!hexagon.contains(&Point(9.5, 0.0)));
+    }
+
+    #[test]
+    fn concave_star() {
+        let star = Polygon::star(Point(50.0, 50.0), 40.0, 10.0, 5);
+
+        assert_eq!(star.points.len(), 10);
+        assert!(star.contains(&Point(50.0, 50.0)));
+        assert!(star.contains(&Point(50.0, 15.0)));
+        // Between the two top points of the star, inside the bounding box.
+        assert!(!star.contains(&Point(70.0, 30.0)));
+        assert_close(star.centroid(), Point(50.0, 50.0));
+    }
+
+    #[test]
+    fn winding_number() {
+        // A pentagram drawn as one self-intersecting outline winds twice around its center.
+        let pentagram = Polygon::new(
+            [0, 2, 4, 1, 3]
+                .iter()
+                .map(|i| Polygon::regular(Point(0.0, 0.0), 10.0, 5).points[*i])
+                .collect(),
+            PathStyle::new(),
+        );
+
+        assert_eq!(pentagram.winding_number(&Point(0.0, 0.0)).abs(), 2);
+        assert!(pentagram.contains(&Point(0.0, 0.0)));
+        assert_eq!(pentagram.winding_number(&Point(20.0, 0.0)), 0);
+    }
+
+    #[test]
+    fn area_and_centroid() {
+        let square = Polygon::new(
+            vec![
+                Point(0.0, 0.0),
+                Point(4.0, 0.0),
+                Point(4.0, 2.0),
+                Point(0.0, 2.0),
+            ],
+            PathStyle::new(),
+        );
+
+        assert_eq!(square.signed_area(), 8.0);
+        assert_close(square.centroid(), Point(2.0, 1.0));
+
+        let reversed = Polygon::new(
+            square.points.iter().rev().copied().collect(),
+            PathStyle::new(),
+        );
+        assert_eq!(reversed.signed_area(), -8.0);
+        assert_close(reversed.centroid(), Point(2.0, 1.0));
+
+        let line = Polygon::new(vec![Point(0.0, 0.0), Point(2.0, 2.0)], PathStyle::new());
+        assert_eq!(line.area(), 0.0);
+        assert_close(line.centroid(), Point(1.0, 1.0));
+    }
+
+    #[test]
+    fn svg() {
+        let mut triangle = Polygon::new(
+            vec![Point(0.0, 0.0), Point(10.0, 0.0), Point(0.0, 10.0)],
+            PathStyle::new(),
+        );
+        assert_eq!(
+            triangle.as_svg(),
+            "<polygon points=\"0.00,0.00 10.00,0.00 0.00,10.00\"/>"
+        );
+
+        triangle.style = PathStyle::new()
+            .color(Color::Hex("#f00"))
+            .stroke(Color::Hex("#000"))
+            .stroke_weight(2.0);
+        assert_eq!(
+            triangle.as_svg(),
+            "<polygon points=\"0.00,0.00 10.00,0.00 0.00,10.00\" fill=\"#f00\" stroke=\"#000\" stroke-width=\"2.00\"/>"
+        );
+    }
+
+    #[test]
+    fn render_blended() {
+        let mut canvas = Canvas::new(10, 10);
+        canvas.fill(Color::Hex("#ffffff"));
+
+        let mut square = Polygon::regular(Point(5.0, 5.0), 4.0, 4);
+        square.style = PathStyle::new().color(Color::Hex("#ff0000"));
+        square.render_blended(&mut canvas);
+
+        assert_eq!(canvas.image().get_pixel(5, 5).0, [255, 0, 0, 255]);
+        assert_eq!(canvas.image().get_pixel(1, 1).0, [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn render_blended_degenerate() {
+        let mut canvas = Canvas::new(10, 10);
+        let style = PathStyle::new()
+            .color(Color::Hex("#ff0000"))
+            .stroke(Color::Hex("#ff0000"));
+
+        for points in [vec![], vec![Point(5.0, 5.0)]] {
+            Polygon::new(points, style).render_blended(&mut canvas);
+        }
+
+        assert!(canvas.image().pixels().all(|pixel| pixel.0 == [0, 0, 0, 0]));
+    }
+}
@@ -3,9 +3,9 @@ use std::{fmt::Display, ops::Range};
 use image::RgbImage;
 use imageproc::rect::Rect;
 
-use crate::palette::color::Color;
+use crate::{palette::color::Color, raster::canvas::Canvas};
 
-use super::{path::Path, path_style::PathStyle, point::Point, shape::Shape};
+use super::{path::Path, path_style::PathStyle, point::Point, shape::Shape, transform::Transform};
 
 #[derive(Debug)]
 pub enum SplitDirection {
@@ -41,10 +41,19 @@ pub struct Rectangle {
     /// Height of the rectangle
     pub height: f64,
 
-    /// Fill color of the rectangle.
+    /// Fill color of the rectangle, black if there is none, the same as in SVG.
     pub color: Option<Color>,
 
+    /// Applied on top of the position and size when the rectangle has been rotated, skewed
+    /// or mirrored.
+    pub transform: Transform,
+
+    /// Rotation in degrees around `rotation_center`, applied before `transform`.
+    #[deprecated(note = "use `rotate` or the `transform` field instead")]
     pub rotation: Option<f64>,
+
+    /// Center of `rotation`, the rectangle is only rotated when both are set.
+    #[deprecated(note = "use `rotate` or the `transform` field instead")]
     pub rotation_center: Option<Point>,
 }
 
@@ -89,10 +98,34 @@ impl Rectangle {
         self.width * self.height
     }
 
-    pub fn rotate(&mut self, rotation: f64, origin: Point) -> Self {
-        self.rotation = Some(rotation);
-        self.rotation_center = Some(origin);
-        *self
+    /// A copy of the rectangle rotated by `rotation` degrees around `origin`.
+    pub fn rotate(&self, rotation: f64, origin: Point) -> Rectangle {
+        self.transform(&Transform::rotate_around(rotation, origin))
+    }
+
+    /// The `transform` with the deprecated `rotation` in front of it, if there is one.
+    #[allow(deprecated)]
+    fn full_transform(&self) -> Transform {
+        match (self.rotation, self.rotation_center) {
+            (Some(rotation), Some(center)) => {
+                Transform::rotate_around(rotation, center).then(&self.transform)
+            }
+            _ => self.transform,
+        }
+    }
+
+    /// The corners of the rectangle, clockwise from the top left, with the transform applied.
+    fn corners(&self) -> [Point; 4] {
+        let (x, y) = (self.position.0, self.position.1);
+        let transform = self.full_transform();
+
+        [
+            Point(x, y),
+            Point(x + self.width, y),
+            Point(x + self.width, y + self.height),
+            Point(x, y + self.height),
+        ]
+        .map(|corner| transform.apply(&corner))
     }
 
     /// Returns a range that starts at the x position of the rectangle
@@ -174,43 +207,80 @@ impl Shape for Rectangle {
             _ => String::from(""),
         };
 
-        let rotation = match (self.rotation, self.rotation_center) {
-            (Some(rotation), Some(center)) => format!(
-                " transform=\"rotate({rotation:.2} {x:.2} {y:.2})\"",
-                rotation = rotation,
-                x = center.0,
-                y = center.1
-            ),
-            _ => String::from(""),
+        let transform = self.full_transform();
+        let transform = if transform.is_identity() {
+            String::from("")
+        } else {
+            format!(" transform=\"{}\"", transform)
         };
 
         format!(
             "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\"{}{}/>",
-            self.position.0, self.position.1, self.width, self.height, fill, rotation
+            self.position.0, self.position.1, self.width, self.height, fill, transform
         )
     }
 
     fn render(&self, image: &mut RgbImage) {
+        let color = self.color.unwrap_or(Color::Hex("#000000"));
+
+        if !self.full_transform().is_identity() {
+            let corners = self
+                .corners()
+                .map(|corner| imageproc::point::Point::new(corner.0 as i32, corner.1 as i32));
+            imageproc::drawing::draw_polygon_mut(image, &corners, color.into());
+            return;
+        }
+
         imageproc::drawing::draw_filled_rect_mut(
             image,
             Rect::at(self.position.0 as i32, self.position.1 as i32)
                 .of_size(self.width as u32, self.height as u32),
-            self.color.unwrap().into(),
+            color.into(),
         );
     }
 
+    fn render_blended(&self, canvas: &mut Canvas) {
+        canvas.fill_polygon(&self.corners(), self.color.unwrap_or(Color::Hex("#000000")));
+    }
+
     fn contains(&self, point: &Point) -> bool {
+        let Some(inverse) = self.full_transform().invert() else {
+            return false;
+        };
+
+        let point = inverse.apply(point);
         self.x_range().contains(&point.0) && self.y_range().contains(&point.1)
     }
 
     fn center(&self) -> Point {
-        Point(
+        self.full_transform().apply(&Point(
             (self.position.0 + self.width) / 2.0,
             (self.position.1 + self.height) / 2.0,
-        )
+        ))
     }
 
     fn bounding_box(&self) -> Option<Rectangle> {
+        if !self.full_transform().is_identity() {
+            let corners = self.corners();
+            let (min_x, min_y, max_x, max_y) = corners.iter().fold(
+                (f64::MAX, f64::MAX, f64::MIN, f64::MIN),
+                |(x1, y1, x2, y2), corner| {
+                    (
+                        x1.min(corner.0),
+                        y1.min(corner.1),
+                        x2.max(corner.0),
+                        y2.max(corner.1),
+                    )
+                },
+            );
+
+            return Some(Rectangle::new(
+                Point(min_x, min_y),
+                max_x - min_x,
+                max_y - min_y,
+            ));
+        }
+
         Some(Rectangle {
             position: self.position,
             width: self.width,
@@ -219,15 +289,43 @@ impl Shape for Rectangle {
             ..Default::default()
         })
     }
+
+    /// Moves and scales the rectangle by changing its position and size, other transforms
+    /// are kept in the `transform` field. A deprecated `rotation` is moved into `transform`.
+    #[allow(deprecated)]
+    fn transform(&self, transform: &Transform) -> Rectangle {
+        let transform = self.full_transform().then(transform);
+        let rect = Rectangle {
+            rotation: None,
+            rotation_center: None,
+            ..*self
+        };
+
+        if transform.is_axis_aligned() {
+            let [a, _, _, d, ..] = transform.matrix();
+
+            Rectangle {
+                position: transform.apply(&self.position),
+                width: self.width * a,
+                height: self.height * d,
+                transform: Transform::identity(),
+                ..rect
+            }
+        } else {
+            Rectangle { transform, ..rect }
+        }
+    }
 }
 
 impl Default for Rectangle {
+    #[allow(deprecated)]
     fn default() -> Self {
         Rectangle {
             position: Point(0., 0.),
             width: 0.0,
             height: 0.0,
             color: None,
+            transform: Transform::identity(),
             rotation: None,
             rotation_center: None,
         }
@@ -249,17 +347,22 @@ impl Display for Rectangle {
     }
 }
 
-impl Into<imageproc::rect::Rect> for Rectangle {
-    fn into(self) -> imageproc::rect::Rect {
-        imageproc::rect::Rect::at(self.position.0 as i32, self.position.1 as i32)
-            .of_size(self.width as u32, self.height as u32)
+impl From<Rectangle> for imageproc::rect::Rect {
+    fn from(rect: Rectangle) -> Self {
+        imageproc::rect::Rect::at(rect.position.0 as i32, rect.position.1 as i32)
+            .of_size(rect.width as u32, rect.height as u32)
     }
 }
 
 #[cfg(test)]
 mod test {
 
-    use crate::shapes::{point::Point, shape::Shape};
+    use image::RgbImage;
+
+    use crate::{
+        raster::canvas::Canvas,
+        shapes::{point::Point, shape::Shape, transform::Transform},
+    };
 
     use super::Rectangle;
 
@@ -347,4 +450,58 @@ mod test {
 
         assert_eq!(rect.center(), Point(75.0, 50.0));
     }
+
+    #[test]
+    fn transform() {
+        let rect = Rectangle::new(Point(10.0, 10.0), 20.0, 10.0);
+
+        let moved =
+            rect.transform(&Transform::translate(5.0, 0.0).then(&Transform::scale(2.0, 2.0)));
+        assert_eq!(moved, Rectangle::new(Point(30.0, 20.0), 40.0, 20.0));
+        assert!(moved.transform.is_identity());
+
+        let rotated = rect.transform(&Transform::rotate_around(90.0, Point(10.0, 10.0)));
+        assert!(rotated.contains(&Point(5.0, 25.0)));
+        assert!(!rotated.contains(&Point(25.0, 15.0)));
+        assert!(rotated
+            .as_svg()
+            .ends_with(" transform=\"matrix(0 1 -1 0 20 0)\"/>"));
+
+        let bounds = rotated.bounding_box().unwrap();
+        assert!(bounds.position.distance_to(&Point(0.0, 10.0)) < 1e-9);
+        assert!((bounds.width - 10.0).abs() < 1e-9 && (bounds.height - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_rotation() {
+        let mut rect = Rectangle::new(Point(10.0, 10.0), 20.0, 10.0);
+        rect.rotation = Some(90.0);
+        rect.rotation_center = Some(Point(10.0, 10.0));
+
+        assert!(rect.contains(&Point(5.0, 25.0)));
+        assert!(rect
+            .as_svg()
+            .ends_with(" transform=\"matrix(0 1 -1 0 20 0)\"/>"));
+
+        let moved = rect.transform(&Transform::translate(5.0, 0.0));
+        assert_eq!(moved.rotation, None);
+        assert!(moved.contains(&Point(10.0, 25.0)));
+    }
+
+    #[test]
+    fn render_without_color() {
+        let rect = Rectangle::new(Point(1.0, 1.0), 2.0, 2.0);
+        assert!(!rect.as_svg().contains("fill"));
+
+        for rect in [rect, rect.rotate(45.0, Point(2.0, 2.0))] {
+            let mut image = RgbImage::from_pixel(4, 4, image::Rgb([255, 255, 255]));
+            rect.render(&mut image);
+            assert_eq!(image.get_pixel(2, 2).0, [0, 0, 0]);
+        }
+
+        let mut canvas = Canvas::new(4, 4);
+        rect.render_blended(&mut canvas);
+        assert_eq!(canvas.image().get_pixel(1, 1).0, [0, 0, 0, 255]);
+    }
 }
@@ -0,0 +1,235 @@
+use super::{point::Point, transform::Transform};
+
+/// How deep curves are split in half while flattening, `2^16` lines per curve at most.
+const MAX_DEPTH: u32 = 16;
+
+/**
+How two consecutive points of a [`Path`](super::path::Path) are connected, either by a
+straight line or by a quadratic or cubic Bezier curve. The curves start and end at the
+points of the path and bend towards their control points.
+
+Example
+
+```
+use art::shapes::{point::Point, segment::Segment};
+
+let curve = Segment::Quadratic(Point(5.0, 10.0));
+let top = curve.point_at(&Point(0.0, 0.0), &Point(10.0, 0.0), 0.5);
+
+assert_eq!(top, Point(5.0, 5.0));
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Segment {
+    /// A straight line.
+    #[default]
+    Line,
+
+    /// A quadratic Bezier curve with one control point, `Q` in SVG.
+    Quadratic(Point),
+
+    /// A cubic Bezier curve with a control point for the start and one for the end, `C`
+    /// in SVG.
+    Cubic(Point, Point),
+}
+
+impl Segment {
+    /// The point at `t`, between `0.0` and `1.0`, along the segment from `from` to `to`.
+    pub fn point_at(&self, from: &Point, to: &Point, t: f64) -> Point {
+        let mut points = self.control_polygon(from, to);
+
+        // De Casteljau's algorithm, repeatedly interpolate between neighbors until one point
+        // is left.
+        while points.len() > 1 {
+            points = points
+                .windows(2)
+                .map(|pair| lerp(&pair[0], &pair[1], t))
+                .collect();
+        }
+
+        points[0]
+    }
+
+    /**
+    Approximates the segment with straight lines, adding the points after `from` up to and
+    including `to`. Curves are split in half until no control point is further than
+    `tolerance` from the line between the ends, so flat parts of a curve get few points and
+    sharp bends get many.
+    */
+    pub(crate) fn flatten(&self, from: &Point, to: &Point, tolerance: f64, out: &mut Vec<Point>) {
+        subdivide(&self.control_polygon(from, to), tolerance, 0, out);
+    }
+
+    /// The points where the segment turns around horizontally or vertically, which together
+    /// with the ends of the segment give its exact bounding box.
+    pub(crate) fn extrema(&self, from: &Point, to: &Point) -> Vec<Point> {
+        let axis = |get: fn(&Point) -> f64| -> Vec<f64> {
+            match self {
+                Segment::Line => vec![],
+                // The derivative is linear, zero where the two halves cancel out.
+                Segment::Quadratic(control) => {
+                    let (a, b, c) = (get(from), get(control), get(to));
+                    let denominator = a - 2.0 * b + c;
+                    if denominator == 0.0 {
+                        vec![]
+                    } else {
+                        vec![(a - b) / denominator]
+                    }
+                }
+                // The derivative is a quadratic polynomial a * t² + b * t + c.
+                Segment::Cubic(control_1, control_2) => {
+                    let (p0, p1, p2, p3) = (get(from), get(control_1), get(control_2), get(to));
+                    let a = 3.0 * (-p0 + 3.0 * p1 - 3.0 * p2 + p3);
+                    let b = 6.0 * (p0 - 2.0 * p1 + p2);
+                    let c = 3.0 * (p1 - p0);
+                    roots(a, b, c)
+                }
+            }
+        };
+
+        let mut extrema = axis(|point| point.0);
+        extrema.extend(axis(|point| point.1));
+        extrema
+            .into_iter()
+            .filter(|t| *t > 0.0 && *t < 1.0)
+            .map(|t| self.point_at(from, to, t))
+            .collect()
+    }
+
+    /// Transforms the control points, which transforms the whole curve since Bezier curves
+    /// are unchanged by affine transforms.
+    pub(crate) fn transform(&self, transform: &Transform) -> Segment {
+        match self {
+            Segment::Line => Segment::Line,
+            Segment::Quadratic(control) => Segment::Quadratic(transform.apply(control)),
+            Segment::Cubic(control_1, control_2) => {
+                Segment::Cubic(transform.apply(control_1), transform.apply(control_2))
+            }
+        }
+    }
+
+    /// The ends of the segment with the control points in between.
+    fn control_polygon(&self, from: &Point, to: &Point) -> Vec<Point> {
+        match self {
+            Segment::Line => vec![*from, *to],
+            Segment::Quadratic(control) => vec![*from, *control, *to],
+            Segment::Cubic(control_1, control_2) => vec![*from, *control_1, *control_2, *to],
+        }
+    }
+}
+
+fn lerp(a: &Point, b: &Point, t: f64) -> Point {
+    Point(a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+/// The real roots of `a * t² + b * t + c`.
+fn roots(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if a.abs() < 1e-12 {
+        return if b == 0.0 { vec![] } else { vec![-c / b] };
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return vec![];
+    }
+
+    let root = discriminant.sqrt();
+    vec![(-b + root) / (2.0 * a), (-b - root) / (2.0 * a)]
+}
+
+/// Distance from `point` to the infinite line through `a` and `b`.
+fn distance_to_line(point: &Point, a: &Point, b: &Point) -> f64 {
+    let length = a.distance_to(b);
+    if length == 0.0 {
+        return point.distance_to(a);
+    }
+
+    ((b.0 - a.0) * (a.1 - point.1) - (a.0 - point.0) * (b.1 - a.1)).abs() / length
+}
+
+fn subdivide(points: &[Point], tolerance: f64, depth: u32, out: &mut Vec<Point>) {
+    let (first, last) = (&points[0], &points[points.len() - 1]);
+    let flat = points[1..points.len() - 1]
+        .iter()
+        .all(|point| distance_to_line(point, first, last) <= tolerance);
+
+    if flat || depth >= MAX_DEPTH {
+        out.push(*last);
+        return;
+    }
+
+    // Split the curve in half with De Casteljau's algorithm, the first points of every
+    // round make up the first half and the last points the second half.
+    let mut left = vec![*first];
+    let mut right = vec![*last];
+    let mut round = points.to_vec();
+    while round.len() > 1 {
+        round = round
+            .windows(2)
+            .map(|pair| lerp(&pair[0], &pair[1], 0.5))
+            .collect();
+        left.push(round[0]);
+        right.push(round[round.len() - 1]);
+    }
+    right.reverse();
+
+    subdivide(&left, tolerance, depth + 1, out);
+    subdivide(&right, tolerance, depth + 1, out);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::shapes::point::Point;
+
+    use super::Segment;
+
+    #[test]
+    fn point_at() {
+        let (from, to) = (Point(0.0, 0.0), Point(30.0, 0.0));
+        let curve = Segment::Cubic(Point(0.0, 30.0), Point(30.0, 30.0));
+
+        assert_eq!(curve.point_at(&from, &to, 0.0), from);
+        assert_eq!(curve.point_at(&from, &to, 1.0), to);
+        assert_eq!(curve.point_at(&from, &to, 0.5), Point(15.0, 22.5));
+        assert_eq!(Segment::Line.point_at(&from, &to, 0.25), Point(7.5, 0.0));
+    }
+
+    #[test]
+    fn flatten() {
+        let (from, to) = (Point(0.0, 0.0), Point(100.0, 0.0));
+        let curve = Segment::Quadratic(Point(50.0, 100.0));
+
+        let mut coarse = vec![];
+        curve.flatten(&from, &to, 5.0, &mut coarse);
+        let mut fine = vec![];
+        curve.flatten(&from, &to, 0.1, &mut fine);
+
+        assert_eq!(coarse.last(), Some(&to));
+        assert!(coarse.len() < fine.len());
+
+        // Every point is on the curve.
+        for point in &fine {
+            let t = point.0 / 100.0;
+            assert!((point.1 - curve.point_at(&from, &to, t).1).abs() < 1e-9);
+        }
+
+        let mut line = vec![];
+        Segment::Line.flatten(&from, &to, 0.1, &mut line);
+        assert_eq!(line, vec![to]);
+    }
+
+    #[test]
+    fn extrema() {
+        let (from, to) = (Point(0.0, 0.0), Point(10.0, 0.0));
+
+        let quadratic = Segment::Quadratic(Point(5.0, -10.0));
+        assert_eq!(quadratic.extrema(&from, &to), vec![Point(5.0, -5.0)]);
+
+        let cubic = Segment::Cubic(Point(-10.0, 10.0), Point(20.0, 10.0));
+        let extrema = cubic.extrema(&from, &to);
+        assert_eq!(extrema.len(), 3);
+        assert!(extrema.iter().any(|point| point.0 < -2.0));
+        assert!(extrema.iter().any(|point| point.0 > 12.0));
+        assert!(extrema.iter().any(|point| (point.1 - 7.5).abs() < 1e-9));
+    }
+}
@@ -1,4 +1,6 @@
-use super::{point::Point, rectangle::Rectangle};
+use crate::raster::canvas::Canvas;
+
+use super::{point::Point, rectangle::Rectangle, transform::Transform};
 
 /// Generic shape definition, can be a Circle, Rectangle, Path, etc
 pub trait Shape {
@@ -6,6 +8,10 @@ pub trait Shape {
     fn as_svg(&self) -> String;
     fn render(&self, image: &mut image::RgbImage);
 
+    /// Render this shape onto a [`Canvas`], blending it with what is already there using
+    /// the alpha of its colors. Fills and strokes follow the same defaults as the SVG output.
+    fn render_blended(&self, canvas: &mut Canvas);
+
     /// Center Point of this shape
     fn center(&self) -> Point;
 
@@ -16,4 +22,9 @@ pub trait Shape {
 
     /// True if the given shape contains {point}, otherwise false.
     fn contains(&self, point: &Point) -> bool;
+
+    /// A copy of this shape with `transform` applied after any transform it already has.
+    fn transform(&self, transform: &Transform) -> Self
+    where
+        Self: Sized;
 }
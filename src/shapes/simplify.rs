@@ -0,0 +1,259 @@
+use super::{path::Path, point::Point};
+
+// Resampling and simplification, to get rid of the thousands of points a path traced one
+// small step at a time ends up with. Curves are flattened first, so the results only
+// contain straight lines.
+impl Path {
+    /**
+    Redistributes the points so they are `spacing` apart along the path, measured along
+    the path and not in a straight line. The first and last point are kept, so the last
+    gap can be shorter, and closed paths stay closed. A `spacing` that is not a positive
+    finite number returns the path unchanged.
+
+    Example
+
+    ```
+    use art::shapes::{path::Path, path_style::PathStyle, point::Point};
+
+    let uneven = Path::new(
+        vec![Point(0.0, 0.0), Point(1.0, 0.0), Point(9.0, 0.0), Point(10.0, 0.0)],
+        PathStyle::new(),
+    );
+    let even = uneven.resample(2.5);
+
+    assert_eq!(even.points()[1], Point(2.5, 0.0));
+    assert_eq!(even.points().len(), 5);
+    ```
+    */
+    pub fn resample(&self, spacing: f64) -> Path {
+        if !(spacing > 0.0 && spacing.is_finite()) {
+            return self.clone();
+        }
+
+        let points = self.flatten(spacing / 100.0);
+        if points.len() < 2 {
+            return Path::new(points, self.style);
+        }
+
+        let mut resampled = vec![points[0]];
+        // How far along the current line the next point goes.
+        let mut next = spacing;
+
+        for pair in points.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            let length = from.distance_to(to);
+
+            while next <= length {
+                let t = next / length;
+                resampled.push(Point(
+                    from.0 + (to.0 - from.0) * t,
+                    from.1 + (to.1 - from.1) * t,
+                ));
+                next += spacing;
+            }
+
+            next -= length;
+        }
+
+        // Avoid a tiny last gap when the length is a multiple of the spacing, up to rounding.
+        let last = points[points.len() - 1];
+        let count = resampled.len();
+        match resampled.last_mut() {
+            Some(point) if count > 1 && point.distance_to(&last) < spacing * 1e-6 => *point = last,
+            _ => resampled.push(last),
+        }
+
+        Path::new(resampled, self.style)
+    }
+
+    /**
+    Removes points that hardly change the shape of the path with the Ramer-Douglas-Peucker
+    algorithm. Every removed point is at most `epsilon` from the simplified path, so
+    straight stretches shrink to their ends while corners are kept. An `epsilon` that is not
+    a positive finite number returns the path unchanged.
+
+    Example
+
+    ```
+    use art::shapes::{path::Path, path_style::PathStyle, point::Point};
+
+    let wobbly = Path::new(
+        (0..=100).map(|i| Point(i as f64, (i % 2) as f64 * 0.1)).collect(),
+        PathStyle::new(),
+    );
+
+    assert_eq!(wobbly.simplify(0.5).points(), vec![Point(0.0, 0.0), Point(100.0, 0.0)]);
+    ```
+    */
+    pub fn simplify(&self, epsilon: f64) -> Path {
+        if !(epsilon > 0.0 && epsilon.is_finite()) {
+            return self.clone();
+        }
+
+        let points = self.flatten(epsilon / 2.0);
+        if points.len() < 3 {
+            return Path::new(points, self.style);
+        }
+
+        let mut keep = vec![false; points.len()];
+        keep[0] = true;
+        keep[points.len() - 1] = true;
+
+        // Keep the point furthest from the line between the ends of every stretch if it is
+        // too far away, and look at both halves again. A stack instead of recursion, since
+        // traced paths can have many thousands of points.
+        let mut stretches = vec![(0, points.len() - 1)];
+        while let Some((start, end)) = stretches.pop() {
+            let furthest = (start + 1..end)
+                .map(|i| {
+                    (
+                        i,
+                        distance_to_line(&points[i], &points[start], &points[end]),
+                    )
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            if let Some((i, distance)) = furthest {
+                if distance > epsilon {
+                    keep[i] = true;
+                    stretches.push((start, i));
+                    stretches.push((i, end));
+                }
+            }
+        }
+
+        let simplified = points
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(point, keep)| keep.then_some(point))
+            .collect();
+
+        Path::new(simplified, self.style)
+    }
+}
+
+/// Distance from `point` to the line between `a` and `b`, or to the closest end if the
+/// point is past either end.
+fn distance_to_line(point: &Point, a: &Point, b: &Point) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx * dx + dy * dy;
+    if length == 0.0 {
+        return point.distance_to(a);
+    }
+
+    let t = (((point.0 - a.0) * dx + (point.1 - a.1) * dy) / length).clamp(0.0, 1.0);
+    point.distance_to(&Point(a.0 + dx * t, a.1 + dy * t))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::shapes::{path::Path, path_style::PathStyle, point::Point};
+
+    fn corner() -> Path {
+        Path::new(
+            vec![Point(0.0, 0.0), Point(10.0, 0.0), Point(10.0, 5.0)],
+            PathStyle::new(),
+        )
+    }
+
+    #[test]
+    fn resample() {
+        let resampled = corner().resample(4.0);
+
+        assert_eq!(
+            resampled.points(),
+            vec![
+                Point(0.0, 0.0),
+                Point(4.0, 0.0),
+                Point(8.0, 0.0),
+                Point(10.0, 2.0),
+                Point(10.0, 5.0)
+            ]
+        );
+
+        // The length is a multiple of the spacing, so there is no extra point at the end.
+        let exact = corner().resample(3.0);
+        assert_eq!(exact.points().len(), 6);
+        assert_eq!(exact.points().last(), Some(&Point(10.0, 5.0)));
+
+        let mut closed = corner();
+        closed.add_point(Point(0.0, 0.0));
+        let resampled = closed.resample(1.5);
+        assert_eq!(resampled.points().first(), resampled.points().last());
+    }
+
+    #[test]
+    fn resample_curve() {
+        let mut path = Path::new(vec![Point(0.0, 0.0)], PathStyle::new());
+        path.quadratic_to(Point(50.0, 50.0), Point(100.0, 0.0));
+
+        let resampled = path.resample(2.0);
+
+        let gaps = resampled
+            .points()
+            .windows(2)
+            .map(|pair| pair[0].distance_to(&pair[1]))
+            .collect::<Vec<_>>();
+        // Distances in a straight line, so slightly less than the spacing on the curve.
+        for gap in &gaps[..gaps.len() - 1] {
+            assert!((gap - 2.0).abs() < 0.01, "{gap}");
+        }
+        assert!((path.length() - resampled.length()).abs() < 0.1);
+    }
+
+    #[test]
+    fn simplify() {
+        let mut dense = Path::new(vec![], PathStyle::new());
+        for i in 0..=100 {
+            dense.add_point(Point(i as f64 / 10.0, 0.0));
+        }
+        for i in 1..=50 {
+            dense.add_point(Point(10.0, i as f64 / 10.0));
+        }
+
+        assert_eq!(dense.simplify(0.01).points(), corner().points());
+        assert_eq!(corner().simplify(6.0).points().len(), 2);
+    }
+
+    #[test]
+    fn simplify_closed() {
+        let mut square = Path::new(
+            vec![
+                Point(0.0, 0.0),
+                Point(5.0, 0.1),
+                Point(10.0, 0.0),
+                Point(10.0, 10.0),
+                Point(0.0, 10.0),
+            ],
+            PathStyle::new(),
+        );
+        square.add_point(Point(0.0, 0.0));
+
+        assert_eq!(
+            square.simplify(0.5).points(),
+            vec![
+                Point(0.0, 0.0),
+                Point(10.0, 0.0),
+                Point(10.0, 10.0),
+                Point(0.0, 10.0),
+                Point(0.0, 0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_tolerance() {
+        let mut curve = corner();
+        curve.quadratic_to(Point(20.0, 5.0), Point(20.0, 10.0));
+
+        for value in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let resampled = curve.resample(value);
+            assert_eq!(resampled.points(), curve.points());
+            assert_eq!(resampled.segments(), curve.segments());
+
+            let simplified = curve.simplify(value);
+            assert_eq!(simplified.points(), curve.points());
+            assert_eq!(simplified.segments(), curve.segments());
+        }
+    }
+}
@@ -0,0 +1,470 @@
+use super::{path::Path, point::Point};
+
+/// Added to the diagonal when fitting, so that fits with too few points per span still have
+/// a solution.
+const RIDGE: f64 = 1e-9;
+
+// Smoothing for dense polylines, such as paths traced through a flow field. A path is
+// treated as closed when its last point is the same as its first point. Existing curves
+// are ignored, the points are smoothed as if they were joined by straight lines.
+impl Path {
+    /**
+    Rounds the corners of the path by cutting every corner off at a quarter and three
+    quarters of its edges, `iterations` times. Every iteration almost doubles the number of
+    points. Open paths keep their first and last point, closed paths stay closed.
+    */
+    pub fn chaikin(&self, iterations: usize) -> Path {
+        if iterations == 0 || self.points().len() < 3 {
+            return self.clone();
+        }
+
+        let mut points = self.points().to_vec();
+
+        for _ in 0..iterations {
+            let closed = is_closed(&points);
+            let mut smooth = Vec::with_capacity(points.len() * 2);
+
+            if !closed {
+                smooth.push(points[0]);
+            }
+
+            for (i, pair) in points.windows(2).enumerate() {
+                let (a, b) = (pair[0], pair[1]);
+                if closed || i > 0 {
+                    smooth.push(lerp(&a, &b, 0.25));
+                }
+                if closed || i < points.len() - 2 {
+                    smooth.push(lerp(&a, &b, 0.75));
+                }
+            }
+
+            if closed {
+                smooth.push(smooth[0]);
+            } else {
+                smooth.push(points[points.len() - 1]);
+            }
+
+            points = smooth;
+        }
+
+        Path::new(points, self.style)
+    }
+
+    /**
+    A smooth curve through every point of the path, made of cubic Bezier curves. The curve
+    at each point goes in the direction from the point before to the point after it, which
+    is a uniform Catmull-Rom spline. Closed paths also have no corner where they start.
+    */
+    pub fn catmull_rom(&self) -> Path {
+        let points = self.points();
+        if points.len() < 3 {
+            return self.clone();
+        }
+
+        let closed = is_closed(points);
+        let count = points.len();
+        let neighbor = |i: usize, offset: isize| -> Point {
+            if closed {
+                // The last point is the first point again, skip it when wrapping around.
+                let distinct = count as isize - 1;
+                points[(i as isize + offset).rem_euclid(distinct) as usize]
+            } else {
+                points[(i as isize + offset).clamp(0, count as isize - 1) as usize]
+            }
+        };
+
+        let mut path = Path::new(vec![points[0]], self.style);
+        for i in 0..count - 1 {
+            let (before, from, to, after) = (
+                neighbor(i, -1),
+                neighbor(i, 0),
+                neighbor(i, 1),
+                neighbor(i, 2),
+            );
+
+            path.cubic_to(
+                Point(
+                    from.0 + (to.0 - before.0) / 6.0,
+                    from.1 + (to.1 - before.1) / 6.0,
+                ),
+                Point(
+                    to.0 - (after.0 - from.0) / 6.0,
+                    to.1 - (after.1 - from.1) / 6.0,
+                ),
+                points[i + 1],
+            );
+        }
+
+        path
+    }
+
+    /**
+    Fits a cubic B-spline with `control_points` control points to the points of the path,
+    as close as possible in the least-squares sense, and turns it into cubic Bezier curves.
+    With far fewer control points than points this smooths out noise and gives a much
+    smaller SVG. Open paths need at least 4 control points and closed paths at least 3.
+
+    Example
+
+    ```
+    use art::shapes::{path::Path, path_style::PathStyle, point::Point};
+
+    let zigzag = Path::new(
+        (0..100).map(|i| Point(i as f64, (i % 2) as f64)).collect(),
+        PathStyle::new(),
+    );
+    let smooth = zigzag.fit_bspline(8);
+
+    assert_eq!(smooth.points().len(), 6);
+    ```
+    */
+    pub fn fit_bspline(&self, control_points: usize) -> Path {
+        let points = self.points();
+        let closed = is_closed(points);
+        let samples = if closed {
+            &points[..points.len() - 1]
+        } else {
+            points
+        };
+
+        let minimum = if closed { 3 } else { 4 };
+        let count = control_points.max(minimum).min(samples.len());
+        if count < minimum {
+            return self.clone();
+        }
+
+        let spline = BSpline::fit(samples, count, closed);
+        let first = spline.point_at(0.0);
+        let mut path = Path::new(vec![first], self.style);
+
+        let spans = spline.spans();
+        for span in 0..spans {
+            let u = |t: f64| (span as f64 + t) / spans as f64;
+            let (start, one_third, two_thirds, end) = (
+                spline.point_at(u(0.0)),
+                spline.point_at(u(1.0 / 3.0)),
+                spline.point_at(u(2.0 / 3.0)),
+                spline.point_at(u(1.0)),
+            );
+
+            // Every span is a cubic polynomial, so the Bezier curve through the same four
+            // points is exactly the same curve.
+            let a = Point(
+                27.0 * one_third.0 - 8.0 * start.0 - end.0,
+                27.0 * one_third.1 - 8.0 * start.1 - end.1,
+            );
+            let b = Point(
+                27.0 * two_thirds.0 - start.0 - 8.0 * end.0,
+                27.0 * two_thirds.1 - start.1 - 8.0 * end.1,
+            );
+
+            path.cubic_to(
+                Point((2.0 * a.0 - b.0) / 18.0, (2.0 * a.1 - b.1) / 18.0),
+                Point((2.0 * b.0 - a.0) / 18.0, (2.0 * b.1 - a.1) / 18.0),
+                // Closed paths end exactly where they start, not just up to rounding.
+                if closed && span == spans - 1 {
+                    first
+                } else {
+                    end
+                },
+            );
+        }
+
+        path
+    }
+}
+
+/// A uniform cubic B-spline. Open splines start and end at their first and last control
+/// points, closed splines wrap around.
+struct BSpline {
+    controls: Vec<Point>,
+    closed: bool,
+}
+
+impl BSpline {
+    /// Least-squares fit of `count` control points to `samples`, which are spread over the
+    /// spline by their distance along the polyline.
+    fn fit(samples: &[Point], count: usize, closed: bool) -> BSpline {
+        let mut distances = vec![0.0];
+        let mut points = samples.to_vec();
+        if closed {
+            points.push(samples[0]);
+        }
+        for pair in points.windows(2) {
+            distances.push(distances[distances.len() - 1] + pair[0].distance_to(&pair[1]));
+        }
+        let total = distances[distances.len() - 1].max(f64::EPSILON);
+
+        let mut spline = BSpline {
+            controls: vec![Point(0.0, 0.0); count],
+            closed,
+        };
+
+        // The normal equations, (Aᵀ A) x = Aᵀ p, for both coordinates at once.
+        let mut normal = vec![vec![0.0; count]; count];
+        let mut right = vec![Point(0.0, 0.0); count];
+        for (sample, distance) in samples.iter().zip(&distances) {
+            let weights = spline.basis(distance / total);
+            for (i, a) in &weights {
+                for (j, b) in &weights {
+                    normal[*i][*j] += a * b;
+                }
+                right[*i] = Point(right[*i].0 + a * sample.0, right[*i].1 + a * sample.1);
+            }
+        }
+        for (i, row) in normal.iter_mut().enumerate() {
+            row[i] += RIDGE;
+        }
+
+        spline.controls = solve(normal, right);
+        spline
+    }
+
+    /// The number of polynomial pieces the spline is made of.
+    fn spans(&self) -> usize {
+        if self.closed {
+            self.controls.len()
+        } else {
+            self.controls.len() - 3
+        }
+    }
+
+    /// The point at `u`, between `0.0` at the start and `1.0` at the end of the spline.
+    fn point_at(&self, u: f64) -> Point {
+        self.basis(u)
+            .iter()
+            .fold(Point(0.0, 0.0), |point, (i, weight)| {
+                Point(
+                    point.0 + weight * self.controls[*i].0,
+                    point.1 + weight * self.controls[*i].1,
+                )
+            })
+    }
+
+    /// The four control points that shape the spline at `u`, with their weights.
+    fn basis(&self, u: f64) -> [(usize, f64); 4] {
+        let count = self.controls.len();
+        let spans = self.spans();
+        let scaled = u.clamp(0.0, 1.0) * spans as f64;
+
+        if self.closed {
+            let span = (scaled.floor() as usize).min(spans - 1);
+            let s = scaled - span as f64;
+
+            let weights = [
+                (1.0 - s).powi(3) / 6.0,
+                (3.0 * s.powi(3) - 6.0 * s.powi(2) + 4.0) / 6.0,
+                (-3.0 * s.powi(3) + 3.0 * s.powi(2) + 3.0 * s + 1.0) / 6.0,
+                s.powi(3) / 6.0,
+            ];
+            return std::array::from_fn(|i| ((span + i) % count, weights[i]));
+        }
+
+        // Clamped knots, the first and last knot are repeated four times so the spline
+        // touches its first and last control point.
+        let knot = |i: isize| (i - 3).clamp(0, spans as isize) as f64;
+        let span = (scaled.floor() as isize).min(spans as isize - 1) + 3;
+
+        // Cox-de Boor recursion, building up the weights one degree at a time.
+        let mut weights = [1.0, 0.0, 0.0, 0.0];
+        let mut left = [0.0; 4];
+        let mut right = [0.0; 4];
+        for degree in 1..4 {
+            left[degree] = scaled - knot(span + 1 - degree as isize);
+            right[degree] = knot(span + degree as isize) - scaled;
+
+            let mut saved = 0.0;
+            for r in 0..degree {
+                let temp = weights[r] / (right[r + 1] + left[degree - r]);
+                weights[r] = saved + right[r + 1] * temp;
+                saved = left[degree - r] * temp;
+            }
+            weights[degree] = saved;
+        }
+
+        std::array::from_fn(|i| (span as usize - 3 + i, weights[i]))
+    }
+}
+
+/// Solves `matrix * x = right` with Gaussian elimination.
+fn solve(mut matrix: Vec<Vec<f64>>, mut right: Vec<Point>) -> Vec<Point> {
+    let size = right.len();
+
+    for column in 0..size {
+        let pivot = (column..size)
+            .max_by(|a, b| {
+                matrix[*a][column]
+                    .abs()
+                    .total_cmp(&matrix[*b][column].abs())
+            })
+            .unwrap_or(column);
+        matrix.swap(column, pivot);
+        right.swap(column, pivot);
+
+        let pivot_row = matrix[column].clone();
+        for row in column + 1..size {
+            let factor = matrix[row][column] / pivot_row[column];
+            for (value, pivot) in matrix[row].iter_mut().zip(&pivot_row).skip(column) {
+                *value -= factor * pivot;
+            }
+            right[row] = Point(
+                right[row].0 - factor * right[column].0,
+                right[row].1 - factor * right[column].1,
+            );
+        }
+    }
+
+    let mut solution = vec![Point(0.0, 0.0); size];
+    for row in (0..size).rev() {
+        let (x, y) = (row + 1..size).fold((right[row].0, right[row].1), |(x, y), k| {
+            (
+                x - matrix[row][k] * solution[k].0,
+                y - matrix[row][k] * solution[k].1,
+            )
+        });
+        solution[row] = Point(x / matrix[row][row], y / matrix[row][row]);
+    }
+
+    solution
+}
+
+fn is_closed(points: &[Point]) -> bool {
+    points.len() > 2 && points.first() == points.last()
+}
+
+fn lerp(a: &Point, b: &Point, t: f64) -> Point {
+    Point(a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::shapes::{path::Path, path_style::PathStyle, point::Point, segment::Segment};
+
+    fn square() -> Path {
+        Path::new(
+            vec![
+                Point(0.0, 0.0),
+                Point(10.0, 0.0),
+                Point(10.0, 10.0),
+                Point(0.0, 10.0),
+                Point(0.0, 0.0),
+            ],
+            PathStyle::new(),
+        )
+    }
+
+    #[test]
+    fn chaikin() {
+        let open = Path::new(
+            vec![Point(0.0, 0.0), Point(8.0, 0.0), Point(8.0, 8.0)],
+            PathStyle::new(),
+        );
+        let smooth = open.chaikin(1);
+        assert_eq!(
+            smooth.points(),
+            vec![
+                Point(0.0, 0.0),
+                Point(6.0, 0.0),
+                Point(8.0, 2.0),
+                Point(8.0, 8.0)
+            ]
+        );
+        assert_eq!(open.chaikin(3).points().len(), 10);
+
+        let closed = square().chaikin(2);
+        assert_eq!(closed.points().len(), 17);
+        assert_eq!(closed.points().first(), closed.points().last());
+        assert!(!closed.points().contains(&Point(0.0, 0.0)));
+    }
+
+    #[test]
+    fn catmull_rom() {
+        let zigzag = Path::new(
+            vec![
+                Point(0.0, 0.0),
+                Point(10.0, 5.0),
+                Point(20.0, 0.0),
+                Point(30.0, 5.0),
+            ],
+            PathStyle::new(),
+        );
+        let smooth = zigzag.catmull_rom();
+        assert_eq!(smooth.points(), zigzag.points());
+        assert_eq!(
+            smooth.segment(1),
+            Segment::Cubic(
+                Point(13.333333333333334, 5.0),
+                Point(16.666666666666668, 0.0)
+            )
+        );
+
+        // The curve leaves the start of a closed path in the direction it arrived from.
+        let closed = square().catmull_rom();
+        let (Segment::Cubic(leave, _), Segment::Cubic(_, arrive)) =
+            (closed.segment(0), closed.segment(3))
+        else {
+            panic!("not a curve");
+        };
+        assert_eq!(
+            Point(leave.0 + arrive.0, leave.1 + arrive.1),
+            Point(0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn fit_open_bspline() {
+        let line = Path::new(
+            (0..50)
+                .map(|i| Point(i as f64, 2.0 * i as f64 + 1.0))
+                .collect(),
+            PathStyle::new(),
+        );
+        let fitted = line.fit_bspline(6);
+
+        assert_eq!(fitted.points().len(), 4);
+        assert!(fitted.points()[0].distance_to(&Point(0.0, 1.0)) < 1e-6);
+        for point in fitted.flatten(0.01) {
+            assert!((point.1 - 2.0 * point.0 - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn fit_closed_bspline() {
+        let mut circle = Path::new(
+            (0..200)
+                .map(|i| {
+                    let (sin, cos) = (std::f64::consts::TAU * i as f64 / 200.0).sin_cos();
+                    let noise = if i % 2 == 0 { 0.5 } else { -0.5 };
+                    Point((50.0 + noise) * cos, (50.0 + noise) * sin)
+                })
+                .collect(),
+            PathStyle::new(),
+        );
+        circle.add_point(circle.points()[0]);
+        let fitted = circle.fit_bspline(12);
+
+        assert_eq!(fitted.points().len(), 13);
+        assert_eq!(fitted.points().first(), fitted.points().last());
+        for point in fitted.flatten(0.01) {
+            let radius = point.distance_to(&Point(0.0, 0.0));
+            assert!((radius - 50.0).abs() < 0.2, "{radius}");
+        }
+    }
+
+    #[test]
+    fn too_few_points() {
+        let line = Path::new(vec![Point(0.0, 0.0), Point(1.0, 1.0)], PathStyle::new());
+
+        assert_eq!(line.chaikin(4).points(), line.points());
+        assert_eq!(line.catmull_rom().points(), line.points());
+        assert_eq!(line.fit_bspline(8).points(), line.points());
+
+        // Curves are kept when there is nothing to smooth.
+        let mut curve = Path::new(vec![Point(0.0, 0.0)], PathStyle::new());
+        curve.quadratic_to(Point(5.0, 5.0), Point(10.0, 0.0));
+        for smooth in [curve.chaikin(4), curve.catmull_rom(), curve.fit_bspline(8)] {
+            assert_eq!(smooth.points(), curve.points());
+            assert_eq!(smooth.segments(), curve.segments());
+        }
+    }
+}
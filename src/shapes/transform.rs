@@ -0,0 +1,405 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::point::Point;
+
+/**
+A 2D affine transform, the same as the SVG
+[`transform`](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/transform)
+attribute. It is stored as the matrix
+
+```text
+| a c e |
+| b d f |
+| 0 0 1 |
+```
+
+Transforms are built from translations, rotations, scales and skews, chained with
+[`Transform::then`], and can be applied to points or to any [`Shape`](super::shape::Shape).
+A transform prints as an SVG `matrix()` and can be parsed from any SVG transform list.
+
+Example
+
+```
+use art::shapes::{point::Point, transform::Transform};
+
+let transform = Transform::scale(2.0, 2.0)
+    .then(&Transform::rotate_around(90.0, Point(10.0, 10.0)));
+
+let point = transform.apply(&Point(5.0, 0.0));
+let back = transform.invert().unwrap().apply(&point);
+
+let parsed: Transform = "translate(10 20) rotate(45)".parse().unwrap();
+let attribute = format!("transform=\"{parsed}\"");
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    e: f64,
+    f: f64,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::identity()
+    }
+}
+
+impl Transform {
+    /// A transform with the given matrix values, in the same order as SVG `matrix()`.
+    pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Transform {
+        Transform { a, b, c, d, e, f }
+    }
+
+    /// The transform that leaves everything where it is.
+    pub fn identity() -> Transform {
+        Transform::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    /// Move by `x` and `y`.
+    pub fn translate(x: f64, y: f64) -> Transform {
+        Transform::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    /// Rotate by `degrees` around the origin, clockwise since the y axis points down.
+    pub fn rotate(degrees: f64) -> Transform {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Transform::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Rotate by `degrees` around `center`.
+    pub fn rotate_around(degrees: f64, center: Point) -> Transform {
+        Transform::translate(-center.0, -center.1)
+            .then(&Transform::rotate(degrees))
+            .then(&Transform::translate(center.0, center.1))
+    }
+
+    /// Scale by `x` horizontally and `y` vertically, away from the origin.
+    pub fn scale(x: f64, y: f64) -> Transform {
+        Transform::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    /// Slant vertical lines by `degrees`.
+    pub fn skew_x(degrees: f64) -> Transform {
+        Transform::new(1.0, 0.0, degrees.to_radians().tan(), 1.0, 0.0, 0.0)
+    }
+
+    /// Slant horizontal lines by `degrees`.
+    pub fn skew_y(degrees: f64) -> Transform {
+        Transform::new(1.0, degrees.to_radians().tan(), 0.0, 1.0, 0.0, 0.0)
+    }
+
+    /// The transform that applies this transform first and `other` after it.
+    pub fn then(&self, other: &Transform) -> Transform {
+        let (s, o) = (self, other);
+
+        Transform::new(
+            o.a * s.a + o.c * s.b,
+            o.b * s.a + o.d * s.b,
+            o.a * s.c + o.c * s.d,
+            o.b * s.c + o.d * s.d,
+            o.a * s.e + o.c * s.f + o.e,
+            o.b * s.e + o.d * s.f + o.f,
+        )
+    }
+
+    /// The transform that undoes this one, or `None` if this transform flattens everything
+    /// onto a line or a point.
+    pub fn invert(&self) -> Option<Transform> {
+        let determinant = self.determinant();
+        if determinant.abs() < 1e-12 {
+            return None;
+        }
+
+        let Transform { a, b, c, d, e, f } = *self;
+        Some(Transform::new(
+            d / determinant,
+            -b / determinant,
+            -c / determinant,
+            a / determinant,
+            (c * f - d * e) / determinant,
+            (b * e - a * f) / determinant,
+        ))
+    }
+
+    /// Apply this transform to `point`.
+    pub fn apply(&self, point: &Point) -> Point {
+        Point(
+            self.a * point.0 + self.c * point.1 + self.e,
+            self.b * point.0 + self.d * point.1 + self.f,
+        )
+    }
+
+    /// The matrix values, in the same order as SVG `matrix()`.
+    pub fn matrix(&self) -> [f64; 6] {
+        [self.a, self.b, self.c, self.d, self.e, self.f]
+    }
+
+    /// How much areas are scaled by, negative if the transform mirrors.
+    pub fn determinant(&self) -> f64 {
+        self.a * self.d - self.b * self.c
+    }
+
+    /// True if this transform leaves everything where it is.
+    pub fn is_identity(&self) -> bool {
+        self.is_close(&Transform::identity())
+    }
+
+    /// True if this transform only moves, rotates, mirrors and scales the same amount in
+    /// every direction, so circles stay circles.
+    pub fn is_similarity(&self) -> bool {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        (close(self.a, self.d) && close(self.b, -self.c))
+            || (close(self.a, -self.d) && close(self.b, self.c))
+    }
+
+    /// True if this transform only moves and scales, so axis aligned rectangles stay axis
+    /// aligned without being mirrored.
+    pub(crate) fn is_axis_aligned(&self) -> bool {
+        self.b.abs() < 1e-9 && self.c.abs() < 1e-9 && self.a > 0.0 && self.d > 0.0
+    }
+
+    fn is_close(&self, other: &Transform) -> bool {
+        self.matrix()
+            .iter()
+            .zip(other.matrix())
+            .all(|(a, b)| (a - b).abs() < 1e-9)
+    }
+}
+
+impl Display for Transform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values = self.matrix().map(number);
+        write!(f, "matrix({})", values.join(" "))
+    }
+}
+
+/// A number with up to 6 decimals and without trailing zeros.
+fn number(value: f64) -> String {
+    let formatted = format!("{value:.6}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    match trimmed {
+        "-0" => String::from("0"),
+        _ => trimmed.to_string(),
+    }
+}
+
+/// An error from parsing a [`Transform`] from an SVG transform list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTransformError {
+    /// The string is not a list of `name(arguments)` functions.
+    InvalidSyntax(String),
+
+    /// A function has the wrong number of arguments or an argument that is not a number.
+    InvalidArguments(String),
+
+    /// A function that is not `matrix`, `translate`, `scale`, `rotate`, `skewX` or `skewY`.
+    UnknownFunction(String),
+}
+
+impl Display for ParseTransformError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseTransformError::InvalidSyntax(input) => {
+                write!(f, "invalid transform \"{input}\"")
+            }
+            ParseTransformError::InvalidArguments(input) => {
+                write!(f, "invalid transform arguments \"{input}\"")
+            }
+            ParseTransformError::UnknownFunction(input) => {
+                write!(f, "unknown transform function \"{input}\"")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseTransformError {}
+
+impl FromStr for Transform {
+    type Err = ParseTransformError;
+
+    /// Parse an SVG transform list, such as `"translate(10, 20) rotate(45 50 50)"`. Like in
+    /// SVG, the last function in the list is applied first.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut transform = Transform::identity();
+        let mut rest = input.trim();
+
+        while !rest.is_empty() {
+            let (open, close) = match (rest.find('('), rest.find(')')) {
+                (Some(open), Some(close)) if open < close => (open, close),
+                _ => return Err(ParseTransformError::InvalidSyntax(input.to_string())),
+            };
+
+            let name = rest[..open].trim();
+            let arguments = rest[open + 1..close]
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|argument| !argument.is_empty())
+                .map(str::parse::<f64>)
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| ParseTransformError::InvalidArguments(rest[..=close].to_string()))?;
+
+            let function = match (name, arguments.as_slice()) {
+                ("matrix", &[a, b, c, d, e, f]) => Transform::new(a, b, c, d, e, f),
+                ("translate", &[x]) => Transform::translate(x, 0.0),
+                ("translate", &[x, y]) => Transform::translate(x, y),
+                ("scale", &[scale]) => Transform::scale(scale, scale),
+                ("scale", &[x, y]) => Transform::scale(x, y),
+                ("rotate", &[degrees]) => Transform::rotate(degrees),
+                ("rotate", &[degrees, x, y]) => Transform::rotate_around(degrees, Point(x, y)),
+                ("skewX", &[degrees]) => Transform::skew_x(degrees),
+                ("skewY", &[degrees]) => Transform::skew_y(degrees),
+                ("matrix" | "translate" | "scale" | "rotate" | "skewX" | "skewY", _) => {
+                    return Err(ParseTransformError::InvalidArguments(
+                        rest[..=close].to_string(),
+                    ))
+                }
+                (name, _) => return Err(ParseTransformError::UnknownFunction(name.to_string())),
+            };
+
+            transform = function.then(&transform);
+            rest = rest[close + 1..].trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        }
+
+        Ok(transform)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::shapes::{
+        circle::Circle, path::Path, path_style::PathStyle, point::Point, shape::Shape,
+    };
+
+    use super::{ParseTransformError, Transform};
+
+    fn assert_close(a: Point, b: Point) {
+        assert!(a.distance_to(&b) < 1e-9, "{a} != {b}");
+    }
+
+    #[test]
+    fn basic_transforms() {
+        let point = Point(10.0, 0.0);
+
+        assert_close(
+            Transform::translate(5.0, -5.0).apply(&point),
+            Point(15.0, -5.0),
+        );
+        assert_close(Transform::rotate(90.0).apply(&point), Point(0.0, 10.0));
+        assert_close(
+            Transform::scale(2.0, 3.0).apply(&Point(1.0, 1.0)),
+            Point(2.0, 3.0),
+        );
+        assert_close(
+            Transform::skew_x(45.0).apply(&Point(0.0, 10.0)),
+            Point(10.0, 10.0),
+        );
+        assert_close(Transform::skew_y(45.0).apply(&point), Point(10.0, 10.0));
+        assert_close(
+            Transform::rotate_around(180.0, Point(5.0, 5.0)).apply(&Point(0.0, 0.0)),
+            Point(10.0, 10.0),
+        );
+    }
+
+    #[test]
+    fn compose_and_invert() {
+        let transform = Transform::scale(2.0, 2.0).then(&Transform::translate(1.0, 0.0));
+        assert_close(transform.apply(&Point(1.0, 1.0)), Point(3.0, 2.0));
+
+        let transform = transform
+            .then(&Transform::skew_x(20.0))
+            .then(&Transform::rotate(33.0));
+        let inverse = transform.invert().unwrap();
+        assert_close(
+            inverse.apply(&transform.apply(&Point(7.0, -3.0))),
+            Point(7.0, -3.0),
+        );
+        assert!(transform.then(&inverse).is_identity());
+
+        assert_eq!(Transform::scale(0.0, 1.0).invert(), None);
+    }
+
+    #[test]
+    fn similarity() {
+        assert!(Transform::rotate(30.0)
+            .then(&Transform::scale(2.0, 2.0))
+            .is_similarity());
+        assert!(Transform::scale(-1.0, 1.0).is_similarity());
+        assert!(!Transform::scale(1.0, 2.0).is_similarity());
+        assert!(!Transform::skew_x(10.0).is_similarity());
+    }
+
+    #[test]
+    fn svg_attribute() {
+        assert_eq!(
+            Transform::translate(10.0, -2.5).to_string(),
+            "matrix(1 0 0 1 10 -2.5)"
+        );
+        assert_eq!(Transform::rotate(90.0).to_string(), "matrix(0 1 -1 0 0 0)");
+
+        let transform = Transform::rotate_around(30.0, Point(4.0, 5.0))
+            .then(&Transform::skew_y(10.0))
+            .then(&Transform::scale(1.5, 0.5));
+        let parsed: Transform = transform.to_string().parse().unwrap();
+        let point = Point(12.0, -7.0);
+        assert!(parsed.apply(&point).distance_to(&transform.apply(&point)) < 1e-4);
+    }
+
+    #[test]
+    fn parse_transform_list() {
+        let parsed: Transform = "translate(10, 20) rotate(90)".parse().unwrap();
+        assert_close(parsed.apply(&Point(1.0, 0.0)), Point(10.0, 21.0));
+
+        let parsed: Transform = " scale(2) ,translate(1 1)".parse().unwrap();
+        assert_close(parsed.apply(&Point(0.0, 0.0)), Point(2.0, 2.0));
+
+        let parsed: Transform = "rotate(180 5 5) skewX(0) skewY(0)".parse().unwrap();
+        assert_close(parsed.apply(&Point(0.0, 0.0)), Point(10.0, 10.0));
+
+        assert_eq!("".parse(), Ok(Transform::identity()));
+        assert_eq!(
+            "rotate(1 2)".parse::<Transform>(),
+            Err(ParseTransformError::InvalidArguments("rotate(1 2)".into()))
+        );
+        assert_eq!(
+            "spin(4)".parse::<Transform>(),
+            Err(ParseTransformError::UnknownFunction("spin".into()))
+        );
+        assert_eq!(
+            "scale 2".parse::<Transform>(),
+            Err(ParseTransformError::InvalidSyntax("scale 2".into()))
+        );
+    }
+
+    #[test]
+    fn transform_shapes() {
+        let circle = Circle::new(Point(10.0, 0.0), 5.0);
+
+        let moved = circle.transform(&Transform::rotate(90.0).then(&Transform::scale(2.0, 2.0)));
+        assert_close(moved.center, Point(0.0, 20.0));
+        assert!((moved.radius - 10.0).abs() < 1e-9);
+        assert!(!moved.as_svg().contains("transform"));
+
+        let ellipse = circle.transform(&Transform::scale(2.0, 1.0));
+        assert_close(ellipse.center(), Point(20.0, 0.0));
+        assert!(ellipse.contains(&Point(28.0, 0.0)) && !ellipse.contains(&Point(20.0, 6.0)));
+        assert!(ellipse
+            .as_svg()
+            .contains(" transform=\"matrix(2 0 0 1 0 0)\""));
+
+        let bounds = ellipse.bounding_box().unwrap();
+        assert_close(bounds.position, Point(10.0, -5.0));
+        assert!((bounds.width - 20.0).abs() < 1e-9 && (bounds.height - 10.0).abs() < 1e-9);
+
+        let mut path = Path::new(vec![Point(0.0, 0.0), Point(10.0, 0.0)], PathStyle::new());
+        path.rotate(90.0, Point(0.0, 0.0));
+        assert_close(path.points()[1], Point(0.0, 10.0));
+        assert_close(
+            Point(1.0, 2.0).transform(&Transform::translate(1.0, 1.0)),
+            Point(2.0, 3.0),
+        );
+    }
+}
@@ -1,10 +1,13 @@
-use crate::shapes::{rectangle::Rectangle, shape::Shape};
+use crate::{
+    palette::color::Color,
+    shapes::{rectangle::Rectangle, shape::Shape},
+};
 
 use super::group::Group;
 
 /// The SVG struct contains all the SVG information for a generated artwork.
 /// It is the common interface for all shapes when they are finally rendered.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Document<'a> {
     /// Name of the generated art piece, will be used to control both
     /// output folder and file name.
@@ -41,7 +44,6 @@ impl<'a> Document<'static> {
     svg.save();
     ```
     */
-
     pub fn new(name: &'static str, bounds: Rectangle) -> Document<'a> {
         Document {
             name,
@@ -82,3 +84,144 @@ impl<'a> Document<'static> {
         format!("{}</svg>", self.document)
     }
 }
+
+impl Document<'_> {
+    /**
+    Replace every color in the document with the result of `f`. This covers the `fill`,
+    `stroke` and `stop-color` attributes, and the same properties inside `style`
+    attributes. Colors that were written as hex stay hex, other colors are written in the
+    CSS syntax of the color `f` returns. Values that are not colors, such as `none` or
+    `url(#gradient)`, are left as they are, and so are `<style>` elements.
+
+    The document is already serialized when shapes are added, so this works on the SVG
+    text rather than on the shapes.
+    */
+    pub fn map_colors<F: Fn(Color) -> Color>(&mut self, f: F) {
+        let mut mapped = String::with_capacity(self.document.len());
+        let mut rest = self.document.as_str();
+
+        while let Some((name, start, end)) = next_attribute(rest) {
+            mapped.push_str(&rest[..start]);
+
+            let value = &rest[start..end];
+            match name {
+                "fill" | "stroke" | "stop-color" => mapped.push_str(&map_color(value, &f)),
+                "style" => mapped.push_str(&map_style(value, &f)),
+                _ => mapped.push_str(value),
+            }
+
+            rest = &rest[end..];
+        }
+
+        mapped.push_str(rest);
+        self.document = mapped;
+    }
+}
+
+/// The name, and the start and end of the value, of the first attribute in `svg`.
+fn next_attribute(svg: &str) -> Option<(&str, usize, usize)> {
+    let equals = svg.find("=\"")?;
+    let start = equals + 2;
+    let end = start + svg[start..].find('"')?;
+
+    let name = svg[..equals].trim_end();
+    let name = &name[name
+        .rfind(|c: char| c.is_whitespace() || c == '<')
+        .map_or(0, |i| i + 1)..];
+
+    Some((name, start, end))
+}
+
+/// `value` mapped by `f` if it is a color, in hex if `value` was hex.
+fn map_color<F: Fn(Color) -> Color>(value: &str, f: &F) -> String {
+    match value.parse::<Color>().map(f) {
+        Ok(color) if value.trim_start().starts_with('#') => {
+            color.try_to_hex().unwrap_or_else(|_| color.to_string())
+        }
+        Ok(color) => color.to_string(),
+        Err(_) => value.to_string(),
+    }
+}
+
+/// The declarations of a `style` attribute with the color properties mapped by `f`.
+fn map_style<F: Fn(Color) -> Color>(style: &str, f: &F) -> String {
+    style
+        .split(';')
+        .map(|declaration| match declaration.split_once(':') {
+            Some((property, value))
+                if matches!(property.trim(), "fill" | "stroke" | "stop-color") =>
+            {
+                let indent = &value[..value.len() - value.trim_start().len()];
+                format!("{property}:{indent}{}", map_color(value, f))
+            }
+            _ => declaration.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(";")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        palette::color::Color,
+        shapes::{
+            circle::Circle, path::Path, path_style::PathStyle, point::Point, rectangle::Rectangle,
+        },
+    };
+
+    use super::Document;
+
+    #[test]
+    fn map_colors() {
+        let mut svg = Document::new("test", Rectangle::new(Point(0.0, 0.0), 10.0, 10.0));
+
+        let mut circle = Circle::new(Point(5.0, 5.0), 2.0);
+        circle.set_color(Color::Hex("#ff0000"));
+        svg.add_shape(Box::new(circle));
+        svg.add_shape(Box::new(Path::new(
+            vec![Point(0.0, 0.0), Point(1.0, 1.0)],
+            PathStyle::new()
+                .stroke(Color::HSLa(120, 100.0, 50.0, 0.5))
+                .stroke_weight(2.0),
+        )));
+
+        svg.map_colors(|color| color.with_alpha(0.25));
+        let generated = svg.generate();
+
+        assert!(generated.contains("fill=\"#ff000040\""));
+        assert!(generated.contains("stroke=\"hsla(120, 100.0%, 50.0%, 0.25)\""));
+        assert!(generated.contains("fill=\"none\""));
+        assert!(generated.contains("stroke-width=\"2.00\""));
+        assert!(generated.starts_with("<svg viewBox=\"0 0 10 10\""));
+    }
+
+    #[test]
+    fn map_colors_in_styles_and_gradients() {
+        let mut svg = Document::new("test", Rectangle::new(Point(0.0, 0.0), 10.0, 10.0));
+        svg.document.push_str(concat!(
+            "<linearGradient id=\"fade\"><stop offset=\"0\" stop-color=\"#00f\"/></linearGradient>",
+            "<rect style=\"fill: rgb(255, 0, 0); stroke:none; opacity: 0.5\" stroke=\"url(#fade)\"/>",
+            "<style>rect { fill: #000; }</style>",
+        ));
+
+        svg.map_colors(|color| color.with_alpha(0.5));
+        let generated = svg.generate();
+
+        assert!(generated.contains("stop-color=\"#0000ff80\""));
+        assert!(
+            generated.contains("style=\"fill: rgba(255, 0, 0, 0.50); stroke:none; opacity: 0.5\"")
+        );
+        assert!(generated.contains("stroke=\"url(#fade)\""));
+        assert!(generated.contains("<style>rect { fill: #000; }</style>"));
+    }
+
+    #[test]
+    fn map_colors_to_invalid_hex() {
+        let mut svg = Document::new("test", Rectangle::new(Point(0.0, 0.0), 10.0, 10.0));
+        svg.document.push_str("<rect fill=\"#f00\"/>");
+
+        svg.map_colors(|_| Color::Hex("#nope"));
+
+        assert!(svg.generate().contains("<rect fill=\"#nope\"/>"));
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use crate::shapes::{point::Point, shape::Shape};
+use crate::shapes::{point::Point, shape::Shape, transform::Transform};
 
 use super::group_style::GroupStyle;
 
@@ -19,7 +19,7 @@ use art::{
     svg::{group::Group, group_style::GroupStyle}
 };
 
-let mut g = Group::new(None);
+let mut g = Group::new();
 
 let rect1 = Rectangle::new(Point(0.0, 0.0), 100.0, 100.0);
 let rect2 = Rectangle::new(Point(0.0, 0.0), 100.0, 100.0);
@@ -31,8 +31,7 @@ g.add_shape(Box::new(rect2));
 #[derive(Default, Clone)]
 pub struct Group {
     shapes: String,
-    rotation: Option<f64>,
-    rotation_center: Option<Point>,
+    transform: Transform,
     style: Option<GroupStyle>,
 }
 
@@ -46,8 +45,7 @@ impl Group {
     pub fn new() -> Group {
         Group {
             shapes: String::from(""),
-            rotation: None,
-            rotation_center: None,
+            transform: Transform::identity(),
             style: None,
         }
     }
@@ -57,11 +55,16 @@ impl Group {
         self.shapes = format!("{}{}", self.shapes, shape.as_svg());
     }
 
-    pub fn rotate(&mut self, angle: f64, center: &Point) -> Group {
+    /// A copy of the group rotated by `angle` degrees around `center`.
+    pub fn rotate(&self, angle: f64, center: &Point) -> Group {
+        self.transform(&Transform::rotate_around(angle, *center))
+    }
+
+    /// A copy of the group with `transform` applied after any transform it already has.
+    pub fn transform(&self, transform: &Transform) -> Group {
         Group {
             shapes: self.shapes.clone(),
-            rotation: Some(angle),
-            rotation_center: Some(*center),
+            transform: self.transform.then(transform),
             style: self.style,
         }
     }
@@ -69,8 +72,7 @@ impl Group {
     pub fn set_style(&mut self, style: GroupStyle) -> Group {
         Group {
             shapes: self.shapes.clone(),
-            rotation: self.rotation,
-            rotation_center: self.rotation_center,
+            transform: self.transform,
             style: Some(style),
         }
     }
@@ -82,15 +84,13 @@ impl Group {
             None => String::from(""),
         };
 
-        let rotation = match (self.rotation, self.rotation_center) {
-            (Some(angle), Some(center)) => format!(
-                " transform=\"rotate({})\" transform-origin=\"{} {}\"",
-                angle, center.0, center.1
-            ),
-            _ => String::from(""),
+        let transform = if self.transform.is_identity() {
+            String::from("")
+        } else {
+            format!(" transform=\"{}\"", self.transform)
         };
 
-        format!("<g{style}{rotation}>{}</g>", self.shapes)
+        format!("<g{style}{transform}>{}</g>", self.shapes)
     }
 }
 
@@ -125,10 +125,14 @@ mod test {
 
     #[test]
     fn rotate_group() {
-        let mut g = Group::new();
+        let g = Group::new();
 
-        g.rotate(100.0, &Point(50., 50.));
+        let rotated = g.rotate(100.0, &Point(50., 50.));
 
-        assert_eq!(g.as_svg(), "<g transform=\"rotate(100)\"></g>")
+        assert_eq!(
+            rotated.as_svg(),
+            "<g transform=\"matrix(-0.173648 0.984808 -0.984808 -0.173648 107.922797 9.442021)\"></g>"
+        );
+        assert_eq!(g.as_svg(), "<g></g>");
     }
 }